@@ -0,0 +1,21 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // generated from the C API alone, the test in src/capi keeps the checked
+    // in include/aoc_2025.h in sync so C users don't need the feature to get it
+    #[cfg(feature = "capi")]
+    {
+        println!("cargo:rerun-if-changed=src/capi/mod.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+
+        let dir = std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+        let out = std::env::var("OUT_DIR").expect("set by cargo");
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", dir)).expect("valid cbindgen.toml");
+        cbindgen::Builder::new()
+            .with_src(format!("{}/src/capi/mod.rs", dir))
+            .with_config(config)
+            .generate()
+            .expect("Could not generate C header")
+            .write_to_file(format!("{}/aoc_2025.h", out));
+    }
+}
@@ -0,0 +1,70 @@
+#ifndef AOC_2025_H
+#define AOC_2025_H
+
+/* Generated by cbindgen from src/capi/mod.rs, don't edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result of [`aoc_solve`]. Unless `out` is null, `*out` is set to a string
+ * that has to be released with [`aoc_free`].
+ */
+typedef enum AocStatus {
+  /**
+   * `*out` holds the answer.
+   */
+  AOC_STATUS_OK = 0,
+  /**
+   * `input` is null or not UTF-8.
+   */
+  AOC_STATUS_INVALID_ARGUMENT = 1,
+  /**
+   * There's no solution for this year, day and part.
+   */
+  AOC_STATUS_UNKNOWN_SOLUTION = 2,
+  /**
+   * The input could not be parsed, `*out` holds the error.
+   */
+  AOC_STATUS_PARSE_ERROR = 3,
+  /**
+   * The solver failed, `*out` holds the error.
+   */
+  AOC_STATUS_SOLVE_ERROR = 4,
+  /**
+   * The solver panicked.
+   */
+  AOC_STATUS_PANIC = 5,
+} AocStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solves a part for the NUL terminated `input`, see [`AocStatus`].
+ *
+ * # Safety
+ *
+ * `input` has to be null or a valid NUL terminated string and `out` a valid
+ * pointer to write the result to.
+ */
+enum AocStatus aoc_solve(uint32_t year, uint32_t day, uint32_t part, const char *input, char **out);
+
+/**
+ * Releases a string returned by [`aoc_solve`]. Null is ignored.
+ *
+ * # Safety
+ *
+ * `s` has to come from [`aoc_solve`] and must not be used afterwards.
+ */
+void aoc_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_2025_H */
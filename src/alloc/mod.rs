@@ -0,0 +1,151 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator and counts every allocation going through it.
+pub struct CountingAllocator;
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new
+    }
+}
+
+/// Allocation statistics collected between [`start`] and [`Tracker::stop`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub allocations: usize,
+    pub bytes: usize,
+    /// Highest number of live bytes on top of what was live at [`start`].
+    pub peak: usize,
+}
+
+pub struct Tracker {
+    allocations: usize,
+    bytes: usize,
+    live: usize,
+}
+
+/// Starts tracking allocations. Counters are process wide, so measurements
+/// are only meaningful while nothing else allocates concurrently.
+pub fn start() -> Tracker {
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    Tracker {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: ALLOCATED.load(Ordering::Relaxed),
+        live,
+    }
+}
+
+impl Tracker {
+    pub fn stop(self) -> Stats {
+        Stats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            bytes: ALLOCATED.load(Ordering::Relaxed) - self.bytes,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(self.live),
+        }
+    }
+}
+
+fn human(bytes: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, units[0])
+    } else {
+        format!("{:.1} {}", value, units[unit])
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            human(self.bytes),
+            human(self.peak)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counts_allocations() {
+        let tracker = start();
+        let v = std::hint::black_box(vec![0u8; 4096]);
+        drop(v);
+        let stats = tracker.stop();
+
+        assert!(stats.allocations >= 1);
+        assert!(stats.bytes >= 4096);
+    }
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(human(512), "512 B");
+        assert_eq!(human(2048), "2.0 KiB");
+        assert_eq!(human(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
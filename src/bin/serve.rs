@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use aoc_2025::serve::{Config, Service};
+use clap::Parser;
+
+/// Serves the solutions on localhost: `POST /solve/{day}/{part}[?year=]`
+/// with the puzzle input as body answers with JSON.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    #[arg(short, long, default_value_t = 8025)]
+    port: u16,
+
+    /// Largest accepted input in bytes
+    #[arg(long, default_value_t = Config::default().max_body)]
+    max_body: usize,
+
+    /// Seconds a request may take
+    #[arg(long, default_value_t = Config::default().timeout.as_secs())]
+    timeout: u64,
+
+    /// Solves running at once, more requests are turned away with 503
+    #[arg(long, default_value_t = Config::default().workers)]
+    workers: usize,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let config = Config {
+        max_body: args.max_body,
+        timeout: Duration::from_secs(args.timeout),
+        workers: args.workers,
+    };
+
+    let service = Service::bind(args.port, config)?;
+    eprintln!("Listening on http://127.0.0.1:{}", service.port().unwrap_or(args.port));
+    service.run();
+
+    Ok(())
+}
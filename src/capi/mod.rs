@@ -0,0 +1,153 @@
+//! C API over the solution registry, exported with the `capi` feature from
+//! the shared library built by
+//! `cargo rustc --lib --release --features capi --crate-type cdylib`. The
+//! header is `include/aoc_2025.h`.
+//!
+//! ```c
+//! char *out = NULL;
+//! AocStatus status = aoc_solve(2025, 1, 2, input, &out);
+//! if (status == AOC_STATUS_OK) printf("%s\n", out);
+//! else fprintf(stderr, "%s\n", out);
+//! aoc_free(out);
+//! ```
+
+use std::{
+    ffi::{CStr, CString, c_char},
+    panic,
+};
+
+use crate::runner::{self, Failure, Settings};
+
+/// Result of [`aoc_solve`]. Unless `out` is null, `*out` is set to a string
+/// that has to be released with [`aoc_free`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    /// `*out` holds the answer.
+    Ok = 0,
+    /// `input` is null or not UTF-8.
+    InvalidArgument = 1,
+    /// There's no solution for this year, day and part.
+    UnknownSolution = 2,
+    /// The input could not be parsed, `*out` holds the error.
+    ParseError = 3,
+    /// The solver failed, `*out` holds the error.
+    SolveError = 4,
+    /// The solver panicked.
+    Panic = 5,
+}
+
+fn solve(year: u32, day: u32, part: u32, input: &str) -> Result<String, (AocStatus, String)> {
+    let solution = runner::find(year, day, part).ok_or((
+        AocStatus::UnknownSolution,
+        format!("No solution for {} day {} part {}", year, day, part),
+    ))?;
+
+    match (solution.run)(input.trim_end_matches('\n'), &Settings::default()) {
+        Ok(report) => Ok(report.answer),
+        Err(Failure::Generating(e)) => Err((AocStatus::ParseError, format!("{:#}", e))),
+        Err(Failure::Running(e)) => Err((AocStatus::SolveError, format!("{:#}", e))),
+    }
+}
+
+fn into_raw(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', "")).unwrap_or_default().into_raw()
+}
+
+/// Solves a part for the NUL terminated `input`, see [`AocStatus`].
+///
+/// # Safety
+///
+/// `input` has to be null or a valid NUL terminated string and `out` a valid
+/// pointer to write the result to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: *const c_char,
+    out: *mut *mut c_char,
+) -> AocStatus {
+    if out.is_null() {
+        return AocStatus::InvalidArgument;
+    }
+
+    let input = if input.is_null() {
+        Err((AocStatus::InvalidArgument, "Input is null".to_string()))
+    } else {
+        unsafe { CStr::from_ptr(input) }
+            .to_str()
+            .map_err(|_| (AocStatus::InvalidArgument, "Input is not UTF-8".to_string()))
+    };
+
+    let result = input.and_then(|input| {
+        panic::catch_unwind(|| solve(year, day, part, input))
+            .unwrap_or_else(|_| Err((AocStatus::Panic, "Solver panicked".to_string())))
+    });
+    let (status, message) = match result {
+        Ok(answer) => (AocStatus::Ok, answer),
+        Err(e) => e,
+    };
+
+    unsafe { *out = into_raw(message) };
+    status
+}
+
+/// Releases a string returned by [`aoc_solve`]. Null is ignored.
+///
+/// # Safety
+///
+/// `s` has to come from [`aoc_solve`] and must not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ptr;
+
+    use super::*;
+
+    fn call(year: u32, day: u32, part: u32, input: Option<&CStr>) -> (AocStatus, String) {
+        let mut out = ptr::null_mut();
+        let input = input.map_or(ptr::null(), |i| i.as_ptr());
+
+        let status = unsafe { aoc_solve(year, day, part, input, &mut out) };
+        let message = unsafe { CStr::from_ptr(out) }.to_string_lossy().to_string();
+        unsafe { aoc_free(out) };
+
+        (status, message)
+    }
+
+    #[test]
+    fn solves() {
+        let input = c"L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        assert_eq!(call(2025, 1, 2, Some(input)), (AocStatus::Ok, "6".to_string()));
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(call(2025, 2, 1, Some(c"11-x")).0, AocStatus::ParseError);
+        assert_eq!(call(2025, 13, 1, Some(c"")).0, AocStatus::UnknownSolution);
+        assert_eq!(call(2025, 1, 1, Some(c"\xff")).0, AocStatus::InvalidArgument);
+        assert_eq!(call(2025, 1, 1, None), (AocStatus::InvalidArgument, "Input is null".to_string()));
+
+        assert_eq!(unsafe { aoc_solve(2025, 1, 1, c"".as_ptr(), ptr::null_mut()) }, AocStatus::InvalidArgument);
+        unsafe { aoc_free(ptr::null_mut()) };
+    }
+
+    #[test]
+    fn header_is_current() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc_2025.h"));
+        let checked_in = include_str!("../../include/aoc_2025.h");
+
+        assert!(
+            generated == checked_in,
+            "include/aoc_2025.h is stale, copy {}/aoc_2025.h over it",
+            env!("OUT_DIR")
+        );
+    }
+}
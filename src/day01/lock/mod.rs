@@ -0,0 +1,280 @@
+//! Combination locks with several dials coupled like an odometer. Whenever a
+//! dial completes a revolution, crossing from its last position to 0 or back,
+//! the next dial turns one click in the same direction. The last dial has
+//! nothing to carry into.
+//!
+//! The runner solves it as part 3 of day 1, answering with the combination
+//! and noting the hits of each dial. Plain puzzle inputs work too, they only
+//! turn the first dial.
+
+use std::{borrow::Borrow, fmt::Display, str::FromStr};
+
+use anyhow::{Context, Error, Result};
+
+use super::{Dial, Hits, Op, add_hits};
+use crate::utils::{AocError, Notes};
+
+/// An [`Op`] on one dial of a [`Lock`], written `2L35` for the second dial.
+/// Plain `L68` lines turn the first dial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rotation {
+    /// Index of the dial, starting at 0 for the dial written as `1`.
+    pub dial: usize,
+    pub op: Op,
+}
+
+impl FromStr for Rotation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let dial = match &s[..digits] {
+            "" => 0,
+            n => n
+                .parse::<usize>()
+                .context("Could not parse dial")?
+                .checked_sub(1)
+                .ok_or(AocError::GenericError)
+                .context("Dials are numbered from 1")?,
+        };
+
+        Ok(Rotation {
+            dial,
+            op: Op::from_str(&s[digits..])?,
+        })
+    }
+}
+
+/// Dials turned together, see the module documentation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lock {
+    pub dials: Vec<Dial>,
+}
+
+/// What a [`Lock`] ended up with after all rotations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Combination {
+    /// Hits of each dial, carried clicks included.
+    pub hits: Vec<Hits>,
+    /// Final position of each dial.
+    pub positions: Vec<i32>,
+}
+
+impl Combination {
+    /// The final positions, e.g. `10-0-0`.
+    pub fn code(&self) -> String {
+        self.positions.iter().map(|p| p.to_string()).collect::<Vec<_>>().join("-")
+    }
+
+    /// One line per dial, e.g. `dial 1: 0 landed, 1 passed`.
+    pub fn dials(&self) -> impl Iterator<Item = String> {
+        self.hits
+            .iter()
+            .enumerate()
+            .map(|(i, hits)| format!("dial {}: {} landed, {} passed", i + 1, hits.landed, hits.passed))
+    }
+}
+
+impl Display for Combination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for dial in self.dials() {
+            writeln!(f, "{}", dial)?;
+        }
+        write!(f, "combination {}", self.code())
+    }
+}
+
+/// Day 1 [`super::Params`] for each of the dials, plus how many there are.
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub dial: super::Params,
+    pub dials: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            dial: super::Params::default(),
+            dials: 1,
+        }
+    }
+}
+
+impl Params {
+    pub fn lock(&self) -> Lock {
+        Lock::new(vec![self.dial.dial(); self.dials])
+    }
+}
+
+impl crate::utils::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "dials" => {
+                self.dials = value.parse()?;
+                if self.dials == 0 {
+                    Err(AocError::GenericError).context("A lock needs at least one dial")?;
+                }
+            }
+            _ => self.dial.set(key, value)?,
+        }
+        Ok(())
+    }
+}
+
+impl Lock {
+    pub fn new(dials: Vec<Dial>) -> Self {
+        Self { dials }
+    }
+
+    /// Turns the dials by `rotations`. A carried click counts as a rotation
+    /// of its own, so landing on a target by carry counts too.
+    pub fn run<R: Borrow<Rotation>>(&self, rotations: impl IntoIterator<Item = Result<R>>) -> Result<Combination> {
+        let dials = self.dials.iter().map(Dial::normalized).collect::<Result<Vec<_>>>()?;
+        let mut positions = dials.iter().map(|d| d.start).collect::<Vec<_>>();
+        let mut hits = vec![Hits::default(); dials.len()];
+
+        for rotation in rotations {
+            let rotation = rotation?;
+            let rotation = rotation.borrow();
+            if rotation.dial >= dials.len() {
+                Err(AocError::GenericError).context(format!(
+                    "Rotation of dial {} on a lock with {} dials",
+                    rotation.dial + 1,
+                    dials.len()
+                ))?;
+            }
+
+            let (mut i, mut clicks) = (rotation.dial, rotation.op.clicks());
+            loop {
+                let event = dials[i].event(positions[i], clicks)?;
+                hits[i].passed = add_hits(hits[i].passed, event.passes as u128)?;
+                hits[i].landed = add_hits(hits[i].landed, u128::from(dials[i].targets.contains(&event.end)))?;
+                positions[i] = event.end;
+
+                let carry = dials[i].revolutions(event.start, clicks);
+                if carry == 0 || i + 1 == dials.len() {
+                    break;
+                }
+                (i, clicks) = (i + 1, (carry > 0, carry.unsigned_abs()));
+            }
+        }
+
+        Ok(Combination { hits, positions })
+    }
+}
+
+pub fn input_generator(input: &str) -> Result<Vec<Rotation>> {
+    input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(Rotation::from_str)
+        .collect::<Result<Vec<_>>>()
+}
+
+/// The combination the lock ends up with, noting the hits of each dial.
+pub fn solve_with(input: &[Rotation], params: &Params, notes: &Notes) -> Result<String> {
+    let combination = params.lock().run(input.iter().map(Ok))?;
+    for dial in combination.dials() {
+        notes.add(dial);
+    }
+    Ok(combination.code())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::Params as _;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parses() -> Result<()> {
+        assert_eq!(Rotation::from_str("L68")?, Rotation { dial: 0, op: Op::Left(68) });
+        assert_eq!(Rotation::from_str("2L35")?, Rotation { dial: 1, op: Op::Left(35) });
+        assert_eq!(Rotation::from_str("12R0")?, Rotation { dial: 11, op: Op::Right(0) });
+        assert!(Rotation::from_str("0L35").is_err());
+        assert!(Rotation::from_str("2").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn single_dial() -> Result<()> {
+        let input = input_generator("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n")?;
+        let combination = Params::default().lock().run(input.iter().map(Ok))?;
+
+        assert_eq!(combination.hits, vec![Hits { landed: 3, passed: 6 }]);
+        assert_eq!(combination.positions, vec![32]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn carries() -> Result<()> {
+        let params = Params::with_overrides(&[("dials".to_string(), "3".to_string())])?;
+        let input = input_generator("R60\n2L251\n3R52\n")?;
+        let combination = params.lock().run(input.iter().map(Ok))?;
+
+        assert_eq!(
+            combination.hits,
+            vec![
+                Hits { landed: 0, passed: 1 },
+                Hits { landed: 1, passed: 3 },
+                Hits { landed: 1, passed: 1 }
+            ]
+        );
+        assert_eq!(combination.positions, vec![10, 0, 0]);
+        assert_eq!(
+            combination.to_string(),
+            "dial 1: 0 landed, 1 passed\ndial 2: 1 landed, 3 passed\ndial 3: 1 landed, 1 passed\ncombination 10-0-0"
+        );
+        let notes = Notes::default();
+        assert_eq!(solve_with(&input, &params, &notes)?, "10-0-0");
+        assert_eq!(
+            notes.into_lines(),
+            vec!["dial 1: 0 landed, 1 passed", "dial 2: 1 landed, 3 passed", "dial 3: 1 landed, 1 passed"]
+        );
+        assert!(solve_with(&input_generator("4R1")?, &params, &Notes::default()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn params() -> Result<()> {
+        let set = |key: &str, value: &str| Params::with_overrides(&[(key.to_string(), value.to_string())]);
+
+        assert_eq!(set("start", "0")?.lock().dials, vec![Dial::new(100, 0); 1]);
+        assert!(set("dials", "0").is_err());
+        assert!(set("typo", "1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn counts_like_an_odometer() -> Result<()> {
+        let lock = Lock::new(vec![Dial::new(10, 0); 3]);
+
+        let up = lock.run(input_generator("R123")?.iter().map(Ok))?;
+        assert_eq!(up.positions, vec![3, 2, 1]);
+
+        let down = lock.run(input_generator("L1")?.iter().map(Ok))?;
+        assert_eq!(down.positions, vec![9, 9, 9]);
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn first_dial_matches_a_single_dial(
+            ops in prop::collection::vec(
+                prop_oneof![(-400i32..400).prop_map(Op::Left), (-400i32..400).prop_map(Op::Right)],
+                0..30,
+            ),
+        ) {
+            let dial = Dial::new(100, 50);
+            let rotations = ops.iter().map(|op| Rotation { dial: 0, op: op.clone() }).collect::<Vec<_>>();
+            let combination = Lock::new(vec![dial.clone(); 3]).run(rotations.iter().map(Ok)).unwrap();
+
+            prop_assert_eq!(combination.hits[0], dial.count(ops.iter().map(Ok)).unwrap());
+        }
+    }
+}
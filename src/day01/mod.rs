@@ -1,17 +1,57 @@
-use std::str::FromStr;
+use std::{
+    borrow::Borrow,
+    collections::BTreeSet,
+    fmt::{Debug, Display},
+    io::BufRead,
+    num::ParseIntError,
+    str::FromStr,
+};
 
 use anyhow::{Context, Error, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::utils::AocError;
+use crate::utils::{AocError, stream_lines};
 
-#[derive(Debug)]
-pub enum Op {
-    Left(i32),
-    Right(i32),
+pub mod lock;
+mod reverse;
+mod segments;
+
+pub use lock::{Combination, Lock, Rotation};
+pub use reverse::{Edit, Rule};
+pub use segments::HitTree;
+
+/// Integer types for rotation amounts and dial positions. The arithmetic is
+/// done in `i128`, so every width works up to its extremes.
+pub trait Amount:
+    Copy + Ord + Debug + Display + From<u8> + Into<i128> + TryFrom<i128> + FromStr<Err = ParseIntError>
+{
+}
+
+impl Amount for i32 {}
+impl Amount for i64 {}
+impl Amount for i128 {}
+
+/// Converts back a value that is known to fit, like a position below the modulus.
+fn narrow<T: Amount>(value: i128) -> T {
+    T::try_from(value).unwrap_or_else(|_| unreachable!("{} does not fit", value))
 }
 
-impl FromStr for Op {
+/// Adds `hits` to `count`, failing instead of overflowing.
+fn add_hits(count: usize, hits: u128) -> Result<usize> {
+    usize::try_from(hits)
+        .ok()
+        .and_then(|hits| count.checked_add(hits))
+        .ok_or(AocError::GenericError)
+        .context("Too many hits to count")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op<T = i32> {
+    Left(T),
+    Right(T),
+}
+
+impl<T: Amount> FromStr for Op<T> {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -23,7 +63,7 @@ impl FromStr for Op {
             .context("Could not find direction")?;
         let num = chars
             .collect::<String>()
-            .parse::<i32>()
+            .parse::<T>()
             .context("Could not parse amount")?;
         match dir {
             'R' => Ok(Op::Right(num)),
@@ -33,76 +73,281 @@ impl FromStr for Op {
     }
 }
 
-#[aoc_generator(day01)]
-pub fn input_generator(input: &str) -> Result<Vec<Op>> {
+impl<T: Amount> Op<T> {
+    /// Direction and number of clicks, `true` to the right. Negative amounts
+    /// turn the other way.
+    pub fn clicks(&self) -> (bool, u128) {
+        let (right, amount): (bool, i128) = match self {
+            Op::Left(v) => (false, (*v).into()),
+            Op::Right(v) => (true, (*v).into()),
+        };
+        (right == (amount >= 0), amount.unsigned_abs())
+    }
+}
 
-    let result = input
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(Op::from_str)
-        .collect::<Result<Vec<_>>>()
-        .unwrap();
-    Ok(result)
+impl Op {
+    /// Clicks of the rotation, negative to the left.
+    pub fn diff(&self) -> i64 {
+        match self {
+            Op::Left(v) => -(*v as i64),
+            Op::Right(v) => *v as i64,
+        }
+    }
 }
 
-#[aoc(day01, part1)]
-pub fn solve_part1(input: &[Op]) -> Result<i32> {
-    let mut dial = 50;
-    let mut num = 0;
-
-    for op in input {
-        let diff = match op {
-            Op::Left(v) => -v,
-            Op::Right(v) => *v,
-        };
-        dial += diff;
-        dial = dial.rem_euclid(100);
+/// A dial with positions `0..modulus`, pointing at `start` before the first
+/// rotation. Hits are counted whenever it points at one of the `targets`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dial<T = i32> {
+    pub modulus: T,
+    pub start: T,
+    pub targets: BTreeSet<T>,
+}
+
+/// Hits on the targets of a [`Dial`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hits {
+    /// Rotations that ended on a target.
+    pub landed: usize,
+    /// Clicks onto a target, the last click of a rotation included.
+    pub passed: usize,
+}
+
+/// A single rotation of a [`Dial`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event<T = i32> {
+    pub start: T,
+    pub end: T,
+    /// Clicks onto a target, see [`Hits::passed`].
+    pub passes: usize,
+}
 
-        if dial == 0 {
-            num += 1;
+impl<T: Amount> Dial<T> {
+    /// The dial of the puzzle with a single target at 0.
+    pub fn new(modulus: T, start: T) -> Self {
+        Self {
+            modulus,
+            start,
+            targets: BTreeSet::from([T::from(0)]),
         }
     }
 
-    Ok(num)
-}
+    pub fn with_targets(self, targets: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            targets: targets.into_iter().collect(),
+            ..self
+        }
+    }
 
-#[aoc(day01, part2)]
-pub fn solve_part2(input: &[Op]) -> Result<i32> {
-    let mut dial = 50;
-    let mut num = 0;
-
-    for op in input {
-        let start = dial;
-        let diff = match op {
-            Op::Left(v) => -v,
-            Op::Right(v) => *v,
-        };
-        dial += diff;
+    /// Clicks onto `target` when turning a normalized dial from `position`.
+    fn passes(&self, position: T, (right, clicks): (bool, u128), target: T) -> u128 {
+        let (position, target, modulus): (i128, i128, i128) = (position.into(), target.into(), self.modulus.into());
 
-        num += match (op, start) {
-            (Op::Left(_), 0) => -1,
-            _ => 0,
-        };
+        // the number of clicks until the dial first points at the target, at most a revolution
+        let first = if right {
+            (target - position - 1).rem_euclid(modulus) + 1
+        } else {
+            (position - target - 1).rem_euclid(modulus) + 1
+        } as u128;
+
+        if clicks < first { 0 } else { (clicks - first) / modulus as u128 + 1 }
+    }
+
+    /// The same dial with `start` and `targets` in `0..modulus`.
+    fn normalized(&self) -> Result<Dial<T>> {
+        let modulus: i128 = self.modulus.into();
+        if modulus <= 0 {
+            Err(AocError::GenericError).context("Dial needs at least one position")?;
+        }
+        let wrap = |value: T| narrow::<T>(value.into().rem_euclid(modulus));
+
+        Ok(Dial {
+            modulus: self.modulus,
+            start: wrap(self.start),
+            targets: self.targets.iter().map(|t| wrap(*t)).collect(),
+        })
+    }
+
+    /// What each rotation in `ops` does, computed without turning the dial
+    /// click by click. See [`Dial::simulate`] for the slow version.
+    pub fn events<O: Borrow<Op<T>>>(
+        &self,
+        ops: impl IntoIterator<Item = Result<O>>,
+    ) -> Result<impl Iterator<Item = Result<Event<T>>>> {
+        let dial = self.normalized()?;
+        let mut position = dial.start;
 
-        if dial <= 0 {
-            num += (dial / 100).abs() + 1;
+        Ok(ops.into_iter().map(move |op| {
+            let event = dial.event(position, op?.borrow().clicks())?;
+            position = event.end;
+
+            Ok(event)
+        }))
+    }
+
+    /// Turning a normalized dial by `clicks` from `start`, see [`Op::clicks`].
+    fn event(&self, start: T, clicks: (bool, u128)) -> Result<Event<T>> {
+        let mut passes = 0;
+        for target in &self.targets {
+            passes = add_hits(passes, self.passes(start, clicks, *target))?;
+        }
+
+        // turning left is turning right by the rest of a revolution
+        let (position, modulus): (i128, i128) = (start.into(), self.modulus.into());
+        let step = (clicks.1 % modulus as u128) as i128;
+        let step = if clicks.0 { step } else { (modulus - step) % modulus };
+        let end = if position >= modulus - step { position - (modulus - step) } else { position + step };
+
+        Ok(Event { start, end: narrow(end), passes })
+    }
+
+    /// Full revolutions of a normalized dial turning by `clicks` from
+    /// `start`, negative to the left.
+    fn revolutions(&self, start: T, clicks: (bool, u128)) -> i128 {
+        // crossing from the last position to 0 or back, whichever way it turns
+        let modulus: i128 = self.modulus.into();
+        if clicks.0 {
+            self.passes(start, clicks, T::from(0)) as i128
+        } else {
+            -(self.passes(start, clicks, narrow(modulus - 1)) as i128)
+        }
+    }
+
+    /// Reference for [`Dial::events`] that turns the dial one click at a time.
+    pub fn simulate<O: Borrow<Op<T>>>(&self, ops: impl IntoIterator<Item = Result<O>>) -> Result<Vec<Event<T>>> {
+        let dial = self.normalized()?;
+        let modulus: i128 = dial.modulus.into();
+        let mut position: i128 = dial.start.into();
+        let mut events = vec![];
+
+        for op in ops {
+            let (right, clicks) = op?.borrow().clicks();
+            let start = narrow(position);
+            let mut passes = 0;
+
+            for _ in 0..clicks {
+                position = (position + if right { 1 } else { -1 }).rem_euclid(modulus);
+                passes = add_hits(passes, u128::from(dial.targets.contains(&narrow(position))))?;
+            }
+
+            events.push(Event { start, end: narrow(position), passes });
         }
 
-        if dial >= 100 {
-            num += dial / 100;
+        Ok(events)
+    }
+
+    /// Counts the hits of all rotations in `ops`.
+    pub fn count<O: Borrow<Op<T>>>(&self, ops: impl IntoIterator<Item = Result<O>>) -> Result<Hits> {
+        let targets = self.normalized()?.targets;
+        let mut hits = Hits::default();
+
+        for event in self.events(ops)? {
+            let event = event?;
+            hits.passed = add_hits(hits.passed, event.passes as u128)?;
+            hits.landed = add_hits(hits.landed, u128::from(targets.contains(&event.end)))?;
         }
 
-        dial = dial.rem_euclid(100);
+        Ok(hits)
     }
+}
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub start: i32,
+    pub modulus: i32,
+    /// Positions that count as hits, `0` in the puzzle.
+    pub targets: Vec<i32>,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            start: 50,
+            modulus: 100,
+            targets: vec![0],
+        }
+    }
+}
+
+impl Params {
+    /// The dial in any width, every `i32` fits.
+    pub fn dial<T: Amount>(&self) -> Dial<T> {
+        let widen = |value: i32| narrow::<T>(value.into());
+        Dial::new(widen(self.modulus), widen(self.start)).with_targets(self.targets.iter().map(|t| widen(*t)))
+    }
+}
+
+impl crate::utils::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "start" => self.start = value.parse()?,
+            "modulus" => self.modulus = value.parse()?,
+            "targets" => {
+                self.targets = value
+                    .split(',')
+                    .map(|t| Ok(t.trim().parse()?))
+                    .collect::<Result<Vec<_>>>()?
+            }
+            _ => Err(AocError::UnknownParameter(key.to_string()))?,
+        }
+        Ok(())
+    }
+}
+
+/// Amounts are read as `i128`, so generated inputs beyond `i32` solve too.
+#[aoc_generator(day01)]
+pub fn input_generator(input: &str) -> Result<Vec<Op<i128>>> {
+    parse_ops(input)
+}
+
+/// Parses rotations of any width, e.g. `parse_ops::<i32>` for the puzzle.
+pub fn parse_ops<T: Amount>(input: &str) -> Result<Vec<Op<T>>> {
+    input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(Op::from_str)
+        .collect::<Result<Vec<_>>>()
+}
+
+#[aoc(day01, part1)]
+pub fn solve_part1(input: &[Op<i128>]) -> Result<usize> {
+    solve_part1_with(input, &Params::default())
+}
+
+pub fn solve_part1_with<T: Amount>(input: &[Op<T>], params: &Params) -> Result<usize> {
+    Ok(params.dial().count(input.iter().map(Ok))?.landed)
+}
+
+/// Part 1 over the rotations in `reader`, one line at a time.
+pub fn solve_part1_stream(reader: impl BufRead, params: &Params) -> Result<usize> {
+    Ok(params.dial().count(stream_ops(reader))?.landed)
+}
+
+fn stream_ops(reader: impl BufRead) -> impl Iterator<Item = Result<Op<i128>>> {
+    stream_lines(reader).map(|l| Op::from_str(&l?))
+}
+
+#[aoc(day01, part2)]
+pub fn solve_part2(input: &[Op<i128>]) -> Result<usize> {
+    solve_part2_with(input, &Params::default())
+}
+
+pub fn solve_part2_with<T: Amount>(input: &[Op<T>], params: &Params) -> Result<usize> {
+    Ok(params.dial().count(input.iter().map(Ok))?.passed)
+}
 
-    Ok(num)
+/// Part 2 over the rotations in `reader`, one line at a time.
+pub fn solve_part2_stream(reader: impl BufRead, params: &Params) -> Result<usize> {
+    Ok(params.dial().count(stream_ops(reader))?.passed)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
+    use crate::utils::Params as _;
 
-    fn input() -> Result<Vec<Op>> {
+    fn input<T: Amount>() -> Result<Vec<Op<T>>> {
        let input = "L68
 L30
 R48
@@ -134,4 +379,188 @@ L82
 
         Ok(())
     }
+
+    #[test]
+    fn stream() -> Result<()> {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+
+        assert_eq!(solve_part1_stream(input.as_bytes(), &Params::default())?, 3);
+        assert_eq!(solve_part2_stream(input.as_bytes(), &Params::default())?, 6);
+        assert!(solve_part1_stream("L68\nX1\n".as_bytes(), &Params::default()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn params() -> Result<()> {
+        let input = input::<i64>()?;
+        let params = Params::with_overrides(&[("start".to_string(), "0".to_string())])?;
+
+        assert_eq!(solve_part1_with(&input, &params)?, 0);
+        assert_eq!(solve_part2_with(&input, &params)?, 4);
+        assert!(Params::with_overrides(&[("size".to_string(), "10".to_string())]).is_err());
+        // only the lock has several dials
+        assert!(Params::with_overrides(&[("dials".to_string(), "3".to_string())]).is_err());
+
+        let params = Params::with_overrides(&[("targets".to_string(), "0,32".to_string())])?;
+        assert_eq!(params.dial::<i32>().targets, BTreeSet::from([0, 32]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn targets() -> Result<()> {
+        let input = input()?;
+        // the example ends on 32 after passing it four times
+        let dial = Dial::new(100, 50).with_targets([32]);
+        assert_eq!(dial.count(input.iter().map(Ok))?, Hits { landed: 1, passed: 5 });
+
+        let dial = Dial::new(100, 50).with_targets([0, 32]);
+        assert_eq!(dial.count(input.iter().map(Ok))?, Hits { landed: 4, passed: 11 });
+
+        // with a single position every click is a hit
+        let dial = Dial::new(1, 0);
+        assert_eq!(dial.count(input.iter().map(Ok))?, Hits { landed: 10, passed: 462 });
+        assert!(Dial::new(0, 0).count(input.iter().map(Ok)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn events() -> Result<()> {
+        let dial = Dial::new(100, 50);
+        let input = input()?;
+        let events = dial.events(input.iter().map(Ok))?.collect::<Result<Vec<_>>>()?;
+
+        assert_eq!(events[0], Event { start: 50, end: 82, passes: 1 });
+        assert_eq!(events[2], Event { start: 52, end: 0, passes: 1 });
+        assert_eq!(events, dial.simulate(input.iter().map(Ok))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn events_at_the_edges() -> Result<()> {
+        let cases = [
+            (0, "L1", Event { start: 0, end: 99, passes: 0 }),
+            (0, "L0", Event { start: 0, end: 0, passes: 0 }),
+            (0, "R0", Event { start: 0, end: 0, passes: 0 }),
+            (0, "L100", Event { start: 0, end: 0, passes: 1 }),
+            (0, "R200", Event { start: 0, end: 0, passes: 2 }),
+            (50, "L50", Event { start: 50, end: 0, passes: 1 }),
+            (50, "L150", Event { start: 50, end: 0, passes: 2 }),
+            (50, "R49", Event { start: 50, end: 99, passes: 0 }),
+            (99, "R1", Event { start: 99, end: 0, passes: 1 }),
+        ];
+
+        for (start, op, expected) in cases {
+            let dial = Dial::new(100, start);
+            let ops = [Op::from_str(op)?];
+            let events = dial.events(ops.iter().map(Ok))?.collect::<Result<Vec<_>>>()?;
+
+            assert_eq!(events, vec![expected], "{} from {}", op, start);
+            assert_eq!(dial.simulate(ops.iter().map(Ok))?, vec![expected], "{} from {}", op, start);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn extremes() -> Result<()> {
+        let dial = Dial::new(100, 50);
+        let ops = [Op::Right(i32::MAX), Op::Left(i32::MIN)];
+        let events = dial.events(ops.iter().map(Ok))?.collect::<Result<Vec<_>>>()?;
+
+        // 50 + 2147483647 = 21474836 * 100 + 97, then a negative left turn of 2147483648 clicks to the right
+        assert_eq!(events[0], Event { start: 50, end: 97, passes: 21474836 });
+        assert_eq!(events[1], Event { start: 97, end: 45, passes: 21474837 });
+
+        let dial = Dial::new(i128::MAX, i128::MAX - 1);
+        let ops = [Op::Right(i128::MAX), Op::Left(i128::MIN), Op::Right(1)];
+        let events = dial.events(ops.iter().map(Ok))?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(events[0], Event { start: i128::MAX - 1, end: i128::MAX - 1, passes: 1 });
+        assert_eq!(events[1], Event { start: i128::MAX - 1, end: 0, passes: 2 });
+        assert_eq!(events[2], Event { start: 0, end: 1, passes: 0 });
+
+        let ops = parse_ops::<i64>("R9223372036854775807
+L9223372036854775808
+");
+        assert!(ops.is_err());
+        let ops = parse_ops::<i64>("R9223372036854775807
+L-9223372036854775808
+")?;
+        assert_eq!(ops[1].clicks(), (true, 1 << 63));
+
+        Ok(())
+    }
+
+    #[test]
+    fn counts_without_overflowing() -> Result<()> {
+        // every click is a hit, which no longer fits once the clicks add up
+        let dial = Dial::<i64>::new(1, 0);
+        assert_eq!(dial.count([Op::Right(i64::MAX)].iter().map(Ok))?.passed, i64::MAX as usize);
+        assert!(dial.count(vec![Op::Right(i64::MAX); 3].iter().map(Ok)).is_err());
+
+        let dial = Dial::<i128>::new(1, 0);
+        let e = dial.count([Op::Left(i128::MIN)].iter().map(Ok)).unwrap_err();
+        assert_eq!(e.to_string(), "Too many hits to count");
+
+        Ok(())
+    }
+
+    fn ops() -> impl Strategy<Value = Vec<Op>> {
+        prop::collection::vec(
+            prop_oneof![(1i32..1000).prop_map(Op::Left), (1i32..1000).prop_map(Op::Right)],
+            0..50,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn passing_zero_counts_at_least_landing_on_it(ops in ops()) {
+            let part1 = solve_part1_with(&ops, &Params::default()).unwrap();
+            let part2 = solve_part2_with(&ops, &Params::default()).unwrap();
+
+            prop_assert!(part2 >= part1);
+        }
+
+        #[test]
+        fn events_match_simulation(
+            start in -200i32..200,
+            modulus in 1i32..150,
+            targets in prop::collection::btree_set(-200i32..200, 0..4),
+            ops in prop::collection::vec(
+                prop_oneof![(-400i32..400).prop_map(Op::Left), (-400i32..400).prop_map(Op::Right)],
+                0..30,
+            ),
+        ) {
+            let dial = Dial::new(modulus, start).with_targets(targets);
+            let events = dial.events(ops.iter().map(Ok)).unwrap().collect::<Result<Vec<_>>>().unwrap();
+
+            prop_assert_eq!(events, dial.simulate(ops.iter().map(Ok)).unwrap());
+        }
+
+        #[test]
+        fn widths_agree(
+            start in any::<i32>(),
+            modulus in 1i32..,
+            targets in prop::collection::btree_set(any::<i32>(), 0..4),
+            ops in prop::collection::vec(
+                prop_oneof![any::<i32>().prop_map(Op::Left), any::<i32>().prop_map(Op::Right)],
+                0..30,
+            ),
+        ) {
+            let narrow = Dial::new(modulus, start).with_targets(targets.clone());
+            let wide = Dial::new(modulus as i128, start as i128).with_targets(targets.iter().map(|t| *t as i128));
+            let wide_ops = ops.iter().map(|op| match op {
+                Op::Left(v) => Op::Left(*v as i128 * 3),
+                Op::Right(v) => Op::Right(*v as i128 * 3),
+            });
+            let tripled = ops.iter().flat_map(|op| [op, op, op]);
+
+            let narrow = narrow.count(tripled.map(Ok)).unwrap();
+            let wide = wide.count(wide_ops.map(Ok)).unwrap();
+            prop_assert_eq!(narrow.passed, wide.passed);
+        }
+    }
 }
@@ -0,0 +1,412 @@
+//! Works backwards from hit counts to dials and inputs that produce them.
+//!
+//! Where a rotation ends only depends on the start and the sum of the
+//! rotations before it, so the hits of every start at once follow from
+//! prefix sums. For part 1 that's a histogram of the prefix sums modulo the
+//! dial size. For part 2 every rotation passes `floor((start + x) / modulus)`
+//! targets up to some prefix sum `x`, which is a constant plus a step at
+//! `modulus - x % modulus` when looked at as a function of the start.
+
+use anyhow::Result;
+
+use super::{Dial, Hits, Op};
+
+/// Which count of [`Hits`] a query is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Rotations ending on a target, as in part 1.
+    Landed,
+    /// Clicks onto a target, as in part 2.
+    Passed,
+}
+
+impl Hits {
+    pub fn get(&self, rule: Rule) -> usize {
+        match rule {
+            Rule::Landed => self.landed,
+            Rule::Passed => self.passed,
+        }
+    }
+}
+
+/// Replacing the rotation at `index` with `op`, see [`Dial::minimal_edit`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub index: usize,
+    pub op: Op,
+}
+
+/// Hits on target 0 of a dial whose start moves over `0..modulus`.
+struct ByStart {
+    modulus: i64,
+    /// Part 1 hits of the rotations ending at each residue of their prefix sum.
+    ends: Vec<usize>,
+    /// Part 2 hits shared by all starts.
+    constant: i64,
+    /// Additional part 2 hits from a start on, as a Fenwick tree.
+    steps: Vec<i64>,
+}
+
+impl ByStart {
+    fn new(modulus: i32) -> Self {
+        let modulus = modulus as usize;
+        Self {
+            modulus: modulus as i64,
+            ends: vec![0; modulus],
+            constant: 0,
+            steps: vec![0; modulus + 1],
+        }
+    }
+
+    /// Adds `sign * floor((start + x) / modulus)` to the part 2 hits.
+    fn add_floor(&mut self, x: i64, sign: i64) {
+        self.constant += sign * x.div_euclid(self.modulus);
+
+        let rest = x.rem_euclid(self.modulus);
+        if rest != 0 {
+            let mut i = (self.modulus - rest) as usize + 1;
+            while i < self.steps.len() {
+                self.steps[i] += sign;
+                i += i & i.wrapping_neg();
+            }
+        }
+    }
+
+    /// Adds a rotation from prefix sum `from` to prefix sum `to`.
+    fn add(&mut self, from: i64, to: i64) {
+        match to.cmp(&from) {
+            // clicks onto from + 1 ..= to
+            std::cmp::Ordering::Greater => {
+                self.add_floor(to, 1);
+                self.add_floor(from, -1);
+            }
+            // clicks onto to ..= from - 1
+            std::cmp::Ordering::Less => {
+                self.add_floor(from - 1, 1);
+                self.add_floor(to - 1, -1);
+            }
+            std::cmp::Ordering::Equal => {}
+        }
+        self.ends[to.rem_euclid(self.modulus) as usize] += 1;
+    }
+
+    fn landed(&self, start: i64) -> usize {
+        self.ends[(-start).rem_euclid(self.modulus) as usize]
+    }
+
+    fn passed(&self, start: i64) -> usize {
+        let mut sum = self.constant;
+        let mut i = start.rem_euclid(self.modulus) as usize + 1;
+        while i > 0 {
+            sum += self.steps[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum as usize
+    }
+
+    /// Hits on `targets` from `start`, shifting the dial instead of the targets.
+    fn hits(&self, start: i64, targets: &[i64]) -> Hits {
+        targets.iter().fold(Hits::default(), |hits, t| Hits {
+            landed: hits.landed + self.landed(start - t),
+            passed: hits.passed + self.passed(start - t),
+        })
+    }
+}
+
+fn prefix_sums(ops: &[Op]) -> Vec<i64> {
+    let mut sums = vec![0];
+    for op in ops {
+        sums.push(sums[sums.len() - 1] + op.diff());
+    }
+    sums
+}
+
+impl Dial {
+    /// Hits of `ops` for every start in `0..modulus`, ignoring `self.start`.
+    pub fn hits_by_start(&self, ops: &[Op]) -> Result<Vec<Hits>> {
+        let dial = self.normalized()?;
+        let targets = dial.targets.iter().map(|t| *t as i64).collect::<Vec<_>>();
+        let sums = prefix_sums(ops);
+
+        let mut by_start = ByStart::new(dial.modulus);
+        for pair in sums.windows(2) {
+            by_start.add(pair[0], pair[1]);
+        }
+
+        Ok((0..dial.modulus as i64)
+            .map(|s| by_start.hits(s, &targets))
+            .collect())
+    }
+
+    /// Starts in `0..modulus` for which `ops` hit the targets exactly `count` times.
+    pub fn starts_with(&self, ops: &[Op], rule: Rule, count: usize) -> Result<Vec<i32>> {
+        Ok(self
+            .hits_by_start(ops)?
+            .iter()
+            .enumerate()
+            .filter(|(_, hits)| hits.get(rule) == count)
+            .map(|(start, _)| start as i32)
+            .collect())
+    }
+
+    /// The smallest change to the amount of a single rotation, keeping its
+    /// direction, after which `ops` hit the targets exactly `count` times.
+    /// Ties go to the earliest rotation, `None` if no single change does it.
+    pub fn minimal_edit(&self, ops: &[Op], rule: Rule, count: usize) -> Result<Option<Edit>> {
+        let dial = self.normalized()?;
+        let modulus = dial.modulus as i64;
+        let targets = dial.targets.iter().map(|t| *t as i64).collect::<Vec<_>>();
+        let count = count as i64;
+        let sums = prefix_sums(ops);
+        let events = dial
+            .events(ops.iter().map(Ok))?
+            .collect::<Result<Vec<_>>>()?;
+
+        // hits of the rotations before each one
+        let mut before = vec![Hits::default()];
+        for event in &events {
+            let last = before[before.len() - 1];
+            before.push(Hits {
+                landed: last.landed + usize::from(dial.targets.contains(&event.end)),
+                passed: last.passed + event.passes,
+            });
+        }
+
+        // every further revolution passes each target once more
+        let per_turn = match rule {
+            Rule::Landed => 0,
+            Rule::Passed => targets.len() as i64,
+        };
+
+        let mut best: Option<(i64, Edit)> = None;
+        let mut after = ByStart::new(dial.modulus);
+
+        for (i, op) in ops.iter().enumerate().rev() {
+            let start = events[i].start;
+            let (sign, old) = match op {
+                Op::Left(v) => (-1, *v as i64),
+                Op::Right(v) => (1, *v as i64),
+            };
+
+            // amounts rest + turns * modulus all end on the same position
+            for rest in 0..modulus {
+                let end = (start as i64 + sign * rest).rem_euclid(modulus);
+                let own = Hits {
+                    landed: usize::from(dial.targets.contains(&(end as i32))),
+                    passed: dial.event(start, (sign > 0, rest as u128))?.passes,
+                };
+                let hits = (before[i].get(rule)
+                    + own.get(rule)
+                    + after.hits(end - sums[i + 1], &targets).get(rule))
+                    as i64;
+
+                let turns = if per_turn == 0 {
+                    if hits != count {
+                        continue;
+                    }
+                    // the closest amounts below and above the current one
+                    let below = (old - rest).div_euclid(modulus).max(0);
+                    vec![below, below + 1]
+                } else {
+                    if count < hits || (count - hits) % per_turn != 0 {
+                        continue;
+                    }
+                    vec![(count - hits) / per_turn]
+                };
+
+                for turns in turns {
+                    let Ok(amount) = i32::try_from(rest + turns * modulus) else {
+                        continue;
+                    };
+                    let change = (amount as i64 - old).abs();
+                    if best
+                        .as_ref()
+                        .is_none_or(|(c, e)| change < *c || (change == *c && i < e.index))
+                    {
+                        let op = if sign < 0 {
+                            Op::Left(amount)
+                        } else {
+                            Op::Right(amount)
+                        };
+                        best = Some((change, Edit { index: i, op }));
+                    }
+                }
+            }
+
+            after.add(sums[i], sums[i + 1]);
+        }
+
+        Ok(best.map(|(_, edit)| edit))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use std::str::FromStr;
+
+    fn input() -> Result<Vec<Op>> {
+        "L68 L30 R48 L5 R60 L55 L1 L99 R14 L82"
+            .split(' ')
+            .map(Op::from_str)
+            .collect()
+    }
+
+    fn amount(op: &Op) -> i64 {
+        match op {
+            Op::Left(v) | Op::Right(v) => *v as i64,
+        }
+    }
+
+    fn apply(ops: &[Op], edit: &Edit) -> Vec<Op> {
+        let mut ops = ops.to_vec();
+        ops[edit.index] = edit.op.clone();
+        ops
+    }
+
+    fn count(dial: &Dial, ops: &[Op], rule: Rule) -> usize {
+        dial.count(ops.iter().map(Ok)).unwrap().get(rule)
+    }
+
+    /// Tries every amount up to `limit` on every rotation, returning the
+    /// smallest change and its rotation.
+    fn brute_force(
+        dial: &Dial,
+        ops: &[Op],
+        rule: Rule,
+        target: usize,
+        limit: i32,
+    ) -> Option<(i64, usize)> {
+        let mut best: Option<(i64, usize)> = None;
+        for (index, op) in ops.iter().enumerate() {
+            for a in 0..=limit {
+                let edit = Edit {
+                    index,
+                    op: if matches!(op, Op::Left(_)) {
+                        Op::Left(a)
+                    } else {
+                        Op::Right(a)
+                    },
+                };
+                let change = (a as i64 - amount(op)).abs();
+                if count(dial, &apply(ops, &edit), rule) == target
+                    && best.is_none_or(|b| (change, index) < b)
+                {
+                    best = Some((change, index));
+                }
+            }
+        }
+        best
+    }
+
+    fn ops() -> impl Strategy<Value = Vec<Op>> {
+        prop::collection::vec(
+            prop_oneof![
+                (-60i32..60).prop_map(Op::Left),
+                (-60i32..60).prop_map(Op::Right)
+            ],
+            1..8,
+        )
+    }
+
+    #[test]
+    fn starts() -> Result<()> {
+        let input = input()?;
+        let dial = Dial::new(100, 50);
+
+        assert_eq!(
+            dial.hits_by_start(&input)?[50],
+            Hits {
+                landed: 3,
+                passed: 6
+            }
+        );
+        assert!(dial.starts_with(&input, Rule::Landed, 3)?.contains(&50));
+        assert!(dial.starts_with(&input, Rule::Passed, 1000)?.is_empty());
+        assert!(Dial::new(0, 0).hits_by_start(&input).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn edits() -> Result<()> {
+        let input = input()?;
+        let dial = Dial::new(100, 50);
+
+        // nothing to change
+        assert_eq!(
+            dial.minimal_edit(&input, Rule::Passed, 6)?,
+            Some(Edit {
+                index: 0,
+                op: Op::Left(68)
+            })
+        );
+
+        // R48 ends on 0, R47 doesn't
+        let edit = dial.minimal_edit(&input, Rule::Landed, 2)?.unwrap();
+        assert_eq!(count(&dial, &apply(&input, &edit), Rule::Landed), 2);
+        assert_eq!((edit.op.diff() - input[edit.index].diff()).abs(), 1);
+
+        // cheaper than ten more revolutions of the first rotation
+        let edit = dial.minimal_edit(&input, Rule::Passed, 16)?.unwrap();
+        assert_eq!(
+            edit,
+            Edit {
+                index: 4,
+                op: Op::Right(947)
+            }
+        );
+        assert_eq!(count(&dial, &apply(&input, &edit), Rule::Passed), 16);
+
+        // a single rotation lands on 0 at most once
+        assert_eq!(dial.minimal_edit(&input, Rule::Landed, 5)?, None);
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn hits_by_start_match_counting(
+            modulus in 1i32..40,
+            targets in prop::collection::btree_set(-50i32..50, 1..4),
+            ops in ops(),
+        ) {
+            let dial = Dial::new(modulus, 0).with_targets(targets);
+            let hits = dial.hits_by_start(&ops).unwrap();
+
+            for (start, hits) in hits.iter().enumerate() {
+                let dial = Dial { start: start as i32, ..dial.clone() };
+                prop_assert_eq!(*hits, dial.count(ops.iter().map(Ok)).unwrap());
+            }
+        }
+
+        #[test]
+        fn minimal_edit_matches_brute_force(
+            modulus in 1i32..12,
+            start in 0i32..12,
+            targets in prop::collection::btree_set(-12i32..12, 1..3),
+            ops in ops(),
+            passed in any::<bool>(),
+            delta in -1i64..3,
+        ) {
+            let rule = if passed { Rule::Passed } else { Rule::Landed };
+            let dial = Dial::new(modulus, start).with_targets(targets);
+            let target = (count(&dial, &ops, rule) as i64 + delta).max(0) as usize;
+            let limit = 120;
+
+            let edit = dial.minimal_edit(&ops, rule, target).unwrap();
+            if let Some(edit) = &edit {
+                prop_assert_eq!(count(&dial, &apply(&ops, edit), rule), target);
+            }
+
+            let fast = edit.map(|e| ((amount(&e.op) - amount(&ops[e.index])).abs(), e.index, amount(&e.op)));
+            match (fast, brute_force(&dial, &ops, rule, target, limit)) {
+                (Some((change, _, amount)), Some(best)) if amount > limit as i64 => prop_assert!(change <= best.0),
+                (Some((change, index, _)), Some(best)) => prop_assert_eq!((change, index), best),
+                (Some((_, _, amount)), None) => prop_assert!(amount > limit as i64),
+                (None, best) => prop_assert_eq!(best, None),
+            }
+        }
+    }
+}
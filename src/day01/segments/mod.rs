@@ -0,0 +1,257 @@
+//! Hits of any range of rotations without replaying it. Each node of a
+//! segment tree keeps the hits of its rotations for every position the dial
+//! could point at before them, plus how far they turn the dial in total, so a
+//! query only looks up one entry per node on its way down.
+
+use std::ops::Range;
+
+use anyhow::{Context, Result};
+
+use super::{Dial, Hits, Op, Rule, add_hits};
+use crate::utils::AocError;
+
+struct Node {
+    /// Clicks to the right the rotations add up to, modulo the dial size.
+    shift: usize,
+    /// Hits by the position before the first rotation.
+    hits: Vec<Hits>,
+}
+
+/// Answers [`HitTree::hits`] in logarithmic time, built by [`Dial::hit_tree`].
+/// Each of the rotations' leaves and each of the internal nodes above them
+/// keeps `modulus` entries, O(ops × modulus) memory in all, so it's meant for
+/// dials of the puzzle's size.
+pub struct HitTree {
+    modulus: usize,
+    /// Position of the dial before each rotation and after the last one.
+    positions: Vec<usize>,
+    nodes: Vec<Node>,
+}
+
+impl Dial {
+    pub fn hit_tree(&self, ops: &[Op]) -> Result<HitTree> {
+        let dial = self.normalized()?;
+        let modulus = dial.modulus as usize;
+
+        let mut positions = vec![dial.start as usize];
+        for event in dial.events(ops.iter().map(Ok))? {
+            positions.push(event?.end as usize);
+        }
+
+        let leaves = (0..ops.len())
+            .map(|i| {
+                let clicks = ops[i].clicks();
+                let hits = (0..dial.modulus)
+                    .map(|start| {
+                        let event = dial.event(start, clicks)?;
+                        Ok(Hits {
+                            landed: usize::from(dial.targets.contains(&event.end)),
+                            passed: event.passes,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let shift = (positions[i + 1] + modulus - positions[i]) % modulus;
+                Ok(Node { shift, hits })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut tree = HitTree {
+            modulus,
+            positions,
+            nodes: (0..4 * ops.len()).map(|_| Node { shift: 0, hits: vec![] }).collect(),
+        };
+        if !ops.is_empty() {
+            tree.build(1, 0..ops.len(), &mut leaves.into_iter())?;
+        }
+
+        Ok(tree)
+    }
+}
+
+/// Hits of `a` followed by those of `b`, failing instead of overflowing.
+fn add(a: Hits, b: Hits) -> Result<Hits> {
+    Ok(Hits {
+        landed: add_hits(a.landed, b.landed as u128)?,
+        passed: add_hits(a.passed, b.passed as u128)?,
+    })
+}
+
+impl HitTree {
+    /// Fills the subtree of `node` covering `range`, taking the leaves in order.
+    fn build(&mut self, node: usize, range: Range<usize>, leaves: &mut impl Iterator<Item = Node>) -> Result<()> {
+        if range.len() == 1 {
+            if let Some(leaf) = leaves.next() {
+                self.nodes[node] = leaf;
+            }
+            return Ok(());
+        }
+
+        let mid = range.start + range.len() / 2;
+        self.build(2 * node, range.start..mid, leaves)?;
+        self.build(2 * node + 1, mid..range.end, leaves)?;
+
+        let (left, right) = (&self.nodes[2 * node], &self.nodes[2 * node + 1]);
+        let hits = (0..self.modulus)
+            .map(|start| add(left.hits[start], right.hits[(start + left.shift) % self.modulus]))
+            .collect::<Result<Vec<_>>>()?;
+        self.nodes[node] = Node {
+            shift: (left.shift + right.shift) % self.modulus,
+            hits,
+        };
+
+        Ok(())
+    }
+
+    /// Adds the hits of the rotations in `query` to `hits`, visiting them
+    /// left to right while `position` follows the dial.
+    fn collect(
+        &self,
+        node: usize,
+        range: Range<usize>,
+        query: &Range<usize>,
+        position: &mut usize,
+        hits: &mut Hits,
+    ) -> Result<()> {
+        if query.end <= range.start || range.end <= query.start {
+            return Ok(());
+        }
+        if query.start <= range.start && range.end <= query.end {
+            let node = &self.nodes[node];
+            *hits = add(*hits, node.hits[*position])?;
+            *position = (*position + node.shift) % self.modulus;
+            return Ok(());
+        }
+
+        let mid = range.start + range.len() / 2;
+        self.collect(2 * node, range.start..mid, query, position, hits)?;
+        self.collect(2 * node + 1, mid..range.end, query, position, hits)
+    }
+
+    /// Number of rotations the tree was built from.
+    pub fn len(&self) -> usize {
+        self.positions.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hits of the rotations in `range` as part of the whole input, i.e.
+    /// starting wherever the rotations before left the dial.
+    pub fn hits(&self, range: Range<usize>) -> Result<Hits> {
+        let start = *self
+            .positions
+            .get(range.start)
+            .ok_or(AocError::GenericError)
+            .context(format!("No rotation {}", range.start))?;
+        self.hits_from(range, start as i32)
+    }
+
+    /// Hits of the rotations in `range` on their own, with the dial pointing
+    /// at `start` before the first one.
+    pub fn hits_from(&self, range: Range<usize>, start: i32) -> Result<Hits> {
+        if range.start > range.end || range.end > self.len() {
+            Err(AocError::GenericError).context(format!(
+                "Rotations {:?} out of {}",
+                range,
+                self.len()
+            ))?;
+        }
+
+        let mut position = (start as i64).rem_euclid(self.modulus as i64) as usize;
+        let mut hits = Hits::default();
+        self.collect(1, 0..self.len(), &range, &mut position, &mut hits)?;
+
+        Ok(hits)
+    }
+
+    /// The `len` consecutive rotations of the whole input with the most hits
+    /// by `rule`, the earliest of them on ties.
+    pub fn busiest(&self, len: usize, rule: Rule) -> Result<Option<(Range<usize>, Hits)>> {
+        let mut best: Option<(Range<usize>, Hits)> = None;
+        for start in 0..(self.len() + 1).saturating_sub(len.max(1)) {
+            let hits = self.hits(start..start + len)?;
+            if best.as_ref().is_none_or(|(_, b)| hits.get(rule) > b.get(rule)) {
+                best = Some((start..start + len, hits));
+            }
+        }
+        Ok(best)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use std::str::FromStr;
+
+    fn input() -> Result<Vec<Op>> {
+        "L68 L30 R48 L5 R60 L55 L1 L99 R14 L82"
+            .split(' ')
+            .map(Op::from_str)
+            .collect()
+    }
+
+    #[test]
+    fn hits() -> Result<()> {
+        let tree = Dial::new(100, 50).hit_tree(&input()?)?;
+
+        assert_eq!(tree.hits(0..10)?, Hits { landed: 3, passed: 6 });
+        // R48 from 52
+        assert_eq!(tree.hits(2..3)?, Hits { landed: 1, passed: 1 });
+        assert_eq!(tree.hits(4..4)?, Hits::default());
+        // L68 L30 R48 from 0 instead of 50 end on 50 without passing 0
+        assert_eq!(tree.hits_from(0..3, 0)?, Hits::default());
+
+        assert!(tree.hits(3..11).is_err());
+        assert!(tree.hits_from(11..11, 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn busiest() -> Result<()> {
+        let tree = Dial::new(100, 50).hit_tree(&input()?)?;
+
+        // R60 L55 passes 0 twice and lands on it once
+        assert_eq!(tree.busiest(2, Rule::Passed)?, Some((4..6, Hits { landed: 1, passed: 2 })));
+        assert_eq!(tree.busiest(10, Rule::Landed)?, Some((0..10, Hits { landed: 3, passed: 6 })));
+        assert_eq!(tree.busiest(11, Rule::Landed)?, None);
+
+        let empty = Dial::new(100, 50).hit_tree(&[])?;
+        assert!(empty.is_empty());
+        assert_eq!(empty.hits(0..0)?, Hits::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn adds_without_overflowing() {
+        let most = Hits { landed: 1, passed: usize::MAX };
+        assert!(add(most, Hits { landed: 0, passed: 1 }).is_err());
+        assert_eq!(add(most, Hits { landed: 2, passed: 0 }).ok(), Some(Hits { landed: 3, passed: usize::MAX }));
+    }
+
+    proptest! {
+        #[test]
+        fn hits_match_counting(
+            modulus in 1i32..30,
+            start in 0i32..30,
+            targets in prop::collection::btree_set(0i32..30, 0..3),
+            ops in prop::collection::vec(
+                prop_oneof![(-100i32..100).prop_map(Op::Left), (-100i32..100).prop_map(Op::Right)],
+                0..40,
+            ),
+            a in 0usize..41,
+            b in 0usize..41,
+        ) {
+            let range = a.min(b).min(ops.len())..a.max(b).min(ops.len());
+            let dial = Dial::new(modulus, 0).with_targets(targets);
+            let tree = dial.hit_tree(&ops).unwrap();
+
+            let dial = Dial { start, ..dial };
+            let expected = dial.count(ops[range.clone()].iter().map(Ok)).unwrap();
+            prop_assert_eq!(tree.hits_from(range, start).unwrap(), expected);
+        }
+    }
+}
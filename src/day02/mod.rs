@@ -1,98 +1,434 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 use anyhow::{Context, Result, Error};
+use num_bigint::BigUint;
 
-use crate::utils::AocError;
+use crate::utils::{AocError, Cancel, NoProgress, Notes, Progress};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Range {
-    start: i64,
-    end: i64,
+    pub(crate) start: u128,
+    pub(crate) end: u128,
+}
+
+impl Range {
+    /// Parses `start-end` with both bounds written in `radix`.
+    pub fn parse(s: &str, radix: Radix) -> Result<Self> {
+        let mut spl = s.split('-');
+        let mut bound = |name: &str| -> Result<u128> {
+            let digits = spl.next().ok_or(AocError::GenericError).context(format!("Could not find {}", name))?;
+            u128::from_str_radix(digits, radix.0).context(format!("Could not parse {} {} in base {}", name, digits, radix.0))
+        };
+        let start = bound("start")?;
+        let end = bound("end")?;
+
+        Ok(Range { start, end })
+    }
 }
 
 impl FromStr for Range {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut spl = s.split('-');
-        let start = spl.next().ok_or(AocError::GenericError).context("Could not find start")?.parse::<i64>()?;
-        let end = spl.next().ok_or(AocError::GenericError).context("Could not find end")?.parse::<i64>()?;
-
-        Ok(Range { start, end })
+        Range::parse(s, Radix::DECIMAL)
     }
 }
 
 #[aoc_generator(day02)]
 pub fn input_generator(input: &str) -> Result<Vec<Range>> {
-    input.split(',').map(Range::from_str).collect::<Result<Vec<_>>>()
+    parse_ranges(input, Radix::DECIMAL)
 }
 
-fn is_invalid(id: i64, div: usize) -> bool {
-    let s = id.to_string();
-    let l = s.len();
+/// [`input_generator`] reading the IDs in the `radix` of `params`.
+pub fn input_generator_with(input: &str, params: &Params) -> Result<Vec<Range>> {
+    parse_ranges(input, params.radix)
+}
+
+/// Comma separated ranges written in `radix`, e.g. `1f-2a,100-1ff` in hex.
+pub fn parse_ranges(input: &str, radix: Radix) -> Result<Vec<Range>> {
+    input.split(',').map(|r| Range::parse(r, radix)).collect::<Result<Vec<_>>>()
+}
 
-    if !l.is_multiple_of(div) {
-        return false;
+/// Base the IDs are written in, 2 to 36 like [`u128::from_str_radix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Radix(u32);
+
+impl Radix {
+    pub const DECIMAL: Radix = Radix(10);
+
+    pub fn new(radix: u32) -> Result<Self> {
+        if !(2..=36).contains(&radix) {
+            Err(AocError::GenericError).context(format!("Base {} is not between 2 and 36", radix))?;
+        }
+        Ok(Radix(radix))
     }
 
-    let mut subs = vec![];
-    let sublen = l / div;
+    fn pow(self, exp: u32) -> u128 {
+        (self.0 as u128).pow(exp)
+    }
+
+    /// Digits of the longest IDs, 39 for decimal.
+    fn max_len(self) -> u32 {
+        u128::MAX.ilog(self.0 as u128) + 1
+    }
 
-    for i in 0..div {
-        subs.push(s.chars().skip(i * sublen).take(sublen).collect::<String>());
+    fn digits(self, id: u128) -> u32 {
+        id.checked_ilog(self.0 as u128).map_or(1, |l| l + 1)
     }
 
-    let first = subs[0].clone();
-    subs.into_iter().all(|s| s == first)
+    /// `n` written in this base, with lowercase letters.
+    pub fn format(self, mut n: u128) -> String {
+        let mut digits = vec![];
+        loop {
+            digits.push(char::from_digit((n % self.0 as u128) as u32, self.0).unwrap_or('?'));
+            n /= self.0 as u128;
+            if n == 0 {
+                break;
+            }
+        }
+        digits.iter().rev().collect()
+    }
 }
 
-#[aoc(day02, part1)]
-pub fn solve_part1(input: &[Range]) -> Result<i64> {
-    let mut sum = 0;
+impl Default for Radix {
+    fn default() -> Self {
+        Radix::DECIMAL
+    }
+}
 
-    for i in input {
-        for k in i.start..=i.end {
-            if is_invalid(k, 2) {
-                sum += k;
-            }
+/// What to do with ranges that overlap or run backwards, see [`normalize`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Swap reversed bounds and merge overlapping ranges, so every ID counts once.
+    #[default]
+    Merge,
+    /// Fail on any of them.
+    Reject,
+}
+
+impl FromStr for Policy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "merge" => Ok(Policy::Merge),
+            "reject" => Ok(Policy::Reject),
+            _ => Err(AocError::GenericError).context(format!("Unknown policy {}, use merge or reject", s)),
         }
     }
+}
 
-    Ok(sum)
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    /// How to treat overlapping and reversed ranges, `merge` or `reject`.
+    pub ranges: Policy,
+    /// Base the IDs are written in, `2` to `36`.
+    pub radix: Radix,
 }
 
-fn is_invalid2(id: i64) -> bool {
-    let s = id.to_string();
-    let l = s.len();
+impl crate::utils::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "ranges" => self.ranges = value.parse()?,
+            "radix" => self.radix = Radix::new(value.parse()?)?,
+            _ => Err(AocError::UnknownParameter(key.to_string()))?,
+        }
+        Ok(())
+    }
+}
 
-    for i in 2..=l {
-        if is_invalid(id, i) {
-            return true;
+/// A problem with the ranges of an input, by their index in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Issue {
+    /// The range ends before it starts.
+    Reversed(usize),
+    /// Both ranges contain some of the same IDs, duplicates included.
+    Overlap(usize, usize),
+}
+
+impl Issue {
+    fn describe(&self, input: &[Range], radix: Radix) -> String {
+        let range = |i: usize| format!("{} ({}-{})", i + 1, radix.format(input[i].start), radix.format(input[i].end));
+        match *self {
+            Issue::Reversed(i) => format!("range {} is reversed", range(i)),
+            Issue::Overlap(i, j) => format!("ranges {} and {} overlap", range(i), range(j)),
         }
     }
+}
 
-    false
+/// Reversed ranges and overlapping pairs of ranges, reversed ranges taken
+/// the right way around.
+pub fn issues(input: &[Range]) -> Vec<Issue> {
+    let bounds = |i: usize| (input[i].start.min(input[i].end), input[i].start.max(input[i].end));
+    let mut issues = (0..input.len())
+        .filter(|i| input[*i].end < input[*i].start)
+        .map(Issue::Reversed)
+        .collect::<Vec<_>>();
+
+    let mut order = (0..input.len()).collect::<Vec<_>>();
+    order.sort_by_key(|i| bounds(*i));
+
+    // ranges starting before this one that haven't ended yet
+    let mut open: Vec<usize> = vec![];
+    for i in order {
+        open.retain(|j| bounds(*j).1 >= bounds(i).0);
+        issues.extend(open.iter().map(|j| Issue::Overlap(i.min(*j), i.max(*j))));
+        open.push(i);
+    }
+
+    issues.sort();
+    issues
 }
 
-#[aoc(day02, part2)]
-pub fn solve_part2(input: &[Range]) -> Result<i64> {
-    let mut sum = 0;
+/// The ranges to solve for. With [`Policy::Merge`] they come out sorted and
+/// disjoint, [`Policy::Reject`] fails unless they already are disjoint,
+/// naming the offending ranges in `radix`.
+pub fn normalize(input: &[Range], policy: Policy, radix: Radix) -> Result<Vec<Range>> {
+    if policy == Policy::Reject {
+        let issues = issues(input);
+        if !issues.is_empty() {
+            let issues = issues.iter().map(|i| i.describe(input, radix)).collect::<Vec<_>>();
+            Err(AocError::GenericError).context(format!("Rejecting the input, {}", issues.join(", ")))?;
+        }
+        return Ok(input.to_vec());
+    }
+
+    let mut ranges = input
+        .iter()
+        .map(|r| Range {
+            start: r.start.min(r.end),
+            end: r.start.max(r.end),
+        })
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|r| r.start);
 
-    for i in input {
-        for k in i.start..=i.end {
-            if is_invalid2(k) {
-                sum += k;
+    let mut merged: Vec<Range> = vec![];
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if last.end >= range.start => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    Ok(merged)
+}
+
+fn num_ids(range: &Range) -> u64 {
+    let ids = range.end.checked_sub(range.start).map_or(0, |n| n.saturating_add(1));
+    u64::try_from(ids).unwrap_or(u64::MAX)
+}
+
+/// The number `1 0..0 1 0..0 1` that turns a block of `block` digits into
+/// `repeats` copies of it, `None` if even the smallest block would be too large.
+fn repeater(block: u32, repeats: u32, radix: Radix) -> Option<u128> {
+    (0..repeats).try_fold(0u128, |sum, i| sum.checked_add(radix.pow(block * i)))
+}
+
+/// Count and sum of the IDs of `len` digits in `range` that are a block
+/// repeated `repeats` times. These are `block * repeater`, so the blocks in
+/// range form an arithmetic series.
+fn repeated(range: &Range, len: u32, repeats: u32, radix: Radix) -> (u128, BigUint) {
+    let block = len / repeats;
+    let Some(repeater) = repeater(block, repeats, radix) else {
+        return (0, BigUint::ZERO);
+    };
+
+    let lowest = range.start.div_ceil(repeater).max(radix.pow(block - 1));
+    let highest = (range.end / repeater).min(radix.pow(block) - 1);
+    if lowest > highest {
+        return (0, BigUint::ZERO);
+    }
+
+    let count = highest - lowest + 1;
+    let blocks = (BigUint::from(lowest) + highest) * count / 2u32;
+    (count, blocks * repeater)
+}
+
+/// How an ID is made of repeated blocks, see [`classify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Structure {
+    /// The shortest block the ID repeats, the whole ID if it doesn't repeat.
+    pub block: u128,
+    /// Every number of copies of some block that make up the ID, ascending
+    /// and starting with 1 for the ID itself.
+    pub repeats: Vec<u32>,
+}
+
+impl Structure {
+    /// Invalid in part 1, two copies of a block.
+    pub fn is_invalid(&self) -> bool {
+        self.repeats.contains(&2)
+    }
+
+    /// Invalid in part 2, two or more copies of a block.
+    pub fn is_invalid2(&self) -> bool {
+        self.repeats.len() > 1
+    }
+}
+
+/// The blocks of `id` written in `radix`.
+pub fn classify(id: u128, radix: Radix) -> Structure {
+    let len = radix.digits(id);
+
+    // the ID itself is a block of full length, so this always finds one
+    let (block, copies) = (1..=len)
+        .filter(|b| len.is_multiple_of(*b))
+        .map(|b| (id / radix.pow(len - b), len / b))
+        .find(|(block, copies)| repeater(len / copies, *copies, radix).and_then(|r| block.checked_mul(r)) == Some(id))
+        .unwrap_or((id, 1));
+
+    // longer blocks are made of copies of the shortest one
+    let repeats = (1..=copies).filter(|r| copies.is_multiple_of(*r)).collect();
+    Structure { block, repeats }
+}
+
+/// The Möbius function, for counting each ID once however many ways it repeats.
+fn mobius(mut n: u32) -> i32 {
+    let mut result = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if n > 1 { -result } else { result }
+}
+
+/// Number of IDs in `range` by how often their shortest block repeats, for
+/// IDs repeating at least twice. IDs repeating a block of `len / k` digits
+/// include those with shorter blocks, which Möbius inversion takes out.
+pub fn repeat_counts(range: &Range, radix: Radix) -> BTreeMap<u32, u128> {
+    let mut counts = BTreeMap::new();
+    for len in 2..=radix.max_len() {
+        for copies in (2..=len).filter(|c| len.is_multiple_of(*c)) {
+            let count = (copies..=len)
+                .step_by(copies as usize)
+                .filter(|k| len.is_multiple_of(*k))
+                .map(|k| mobius(k / copies) as i128 * repeated(range, len, k, radix).0 as i128)
+                .sum::<i128>();
+            if count > 0 {
+                *counts.entry(copies).or_default() += count as u128;
+            }
+        }
+    }
+    counts
+}
+
+/// Describes the invalid IDs of `range`, counting only those repeating as often as `counted` allows.
+fn describe(range: &Range, radix: Radix, counted: impl Fn(u32) -> bool) -> String {
+    let counts = repeat_counts(range, radix).into_iter().filter(|(c, _)| counted(*c)).collect::<Vec<_>>();
+    let total = counts.iter().map(|(_, n)| n).sum::<u128>();
+
+    let mut out = format!("{}-{}: {} invalid", radix.format(range.start), radix.format(range.end), total);
+    if !counts.is_empty() {
+        let by_copies = counts.iter().map(|(c, n)| format!("{} repeating {}x", n, c)).collect::<Vec<_>>();
+        out.push_str(&format!(" ({})", by_copies.join(", ")));
+    }
+    out
+}
+
+/// Sum of the IDs in `range` that are a block repeated exactly twice.
+fn sum_twice(range: &Range, radix: Radix) -> BigUint {
+    (2..=radix.max_len()).step_by(2).map(|len| repeated(range, len, 2, radix).1).sum()
+}
+
+/// Sum of the IDs in `range` that are a block repeated at least twice.
+/// Summing over all repeat counts would count `111111` three times, so the
+/// counts are weighted by the Möbius function: an ID repeating `n` times is
+/// counted `-sum(mobius(d))` times over the divisors `d > 1` of `n`, i.e. once.
+fn sum_repeated(range: &Range, radix: Radix) -> BigUint {
+    let (mut added, mut removed) = (BigUint::ZERO, BigUint::ZERO);
+    for len in 2..=radix.max_len() {
+        for repeats in (2..=len).filter(|r| len.is_multiple_of(*r)) {
+            match mobius(repeats) {
+                -1 => added += repeated(range, len, repeats, radix).1,
+                1 => removed += repeated(range, len, repeats, radix).1,
+                _ => {}
             }
         }
     }
+    added - removed
+}
+
+fn solve_with(
+    input: &[Range],
+    radix: Radix,
+    cancel: &Cancel,
+    progress: &dyn Progress,
+    notes: &Notes,
+    sum: impl Fn(&Range, Radix) -> BigUint,
+    counted: impl Fn(u32) -> bool,
+) -> Result<BigUint> {
+    let total = input.iter().map(num_ids).fold(0, u64::saturating_add);
+    let mut done = 0;
+    let mut result = BigUint::ZERO;
+
+    for range in input {
+        cancel.check()?;
+        result += sum(range, radix);
+        notes.add(describe(range, radix, &counted));
+        done = num_ids(range).saturating_add(done);
+        progress.report(done, total);
+    }
 
-    Ok(sum)
+    Ok(result)
+}
+
+/// Normalizes `input` by `params`, noting what was merged or swapped.
+fn prepare(input: &[Range], params: &Params, notes: &Notes) -> Result<Vec<Range>> {
+    let ranges = normalize(input, params.ranges, params.radix)?;
+    for issue in issues(input) {
+        notes.add(issue.describe(input, params.radix));
+    }
+    Ok(ranges)
+}
+
+#[aoc(day02, part1)]
+pub fn solve_part1(input: &[Range]) -> Result<BigUint> {
+    solve_part1_with(input, &Params::default(), &Cancel::default(), &NoProgress, &Notes::default())
+}
+
+/// Part 1 with the IDs written in the `radix` of `params`, see [`input_generator_with`].
+pub fn solve_part1_with(
+    input: &[Range],
+    params: &Params,
+    cancel: &Cancel,
+    progress: &dyn Progress,
+    notes: &Notes,
+) -> Result<BigUint> {
+    let ranges = prepare(input, params, notes)?;
+    // two copies of a block are an even number of copies of the shortest one
+    solve_with(&ranges, params.radix, cancel, progress, notes, sum_twice, |c| c.is_multiple_of(2))
+}
+
+#[aoc(day02, part2)]
+pub fn solve_part2(input: &[Range]) -> Result<BigUint> {
+    solve_part2_with(input, &Params::default(), &Cancel::default(), &NoProgress, &Notes::default())
+}
+
+/// Part 2 with the IDs written in the `radix` of `params`, see [`input_generator_with`].
+pub fn solve_part2_with(
+    input: &[Range],
+    params: &Params,
+    cancel: &Cancel,
+    progress: &dyn Progress,
+    notes: &Notes,
+) -> Result<BigUint> {
+    let ranges = prepare(input, params, notes)?;
+    solve_with(&ranges, params.radix, cancel, progress, notes, sum_repeated, |_| true)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::utils::Params as _;
+    use proptest::prelude::*;
 
     fn input() -> Result<Vec<Range>> {
         let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
@@ -102,7 +438,7 @@ mod test {
     #[test]
     fn part1() -> Result<()> {
         let input = input()?;
-        assert_eq!(solve_part1(&input)?, 1227775554);
+        assert_eq!(solve_part1(&input)?, BigUint::from(1227775554u64));
 
         Ok(())
     }
@@ -110,8 +446,244 @@ mod test {
     #[test]
     fn part2() -> Result<()> {
         let input = input()?;
-        assert_eq!(solve_part2(&input)?, 4174379265);
+        assert_eq!(solve_part2(&input)?, BigUint::from(4174379265u64));
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel() -> Result<()> {
+        let input = input()?;
+        let cancel = Cancel::default();
+        cancel.cancel();
+
+        let err = solve_part2_with(&input, &Params::default(), &cancel, &NoProgress, &Notes::default()).unwrap_err();
+        assert!(matches!(err.downcast_ref::<AocError>(), Some(AocError::Cancelled)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn progress() -> Result<()> {
+        struct Last(std::sync::Mutex<(u64, u64)>);
+        impl Progress for Last {
+            fn report(&self, done: u64, total: u64) {
+                *self.0.lock().unwrap() = (done, total);
+            }
+        }
+
+        let input = input_generator("1-200000,300000-300009")?;
+        let last = Last(Default::default());
+        solve_part1_with(&input, &Params::default(), &Cancel::default(), &last, &Notes::default())?;
+
+        Ok(assert_eq!(*last.0.lock().unwrap(), (200010, 200010)))
+    }
+
+    #[test]
+    fn wide_ranges() -> Result<()> {
+        // every 2k digit number with equal halves, 9 * 10^(k-1) of them per length
+        let input = input_generator("1-99999999999999999999")?;
+        let expected = (1..=10u32)
+            .map(|k| {
+                let (lowest, highest) = (10u128.pow(k - 1), 10u128.pow(k) - 1);
+                BigUint::from(lowest + highest) * (highest - lowest + 1) / 2u32 * (10u128.pow(k) + 1)
+            })
+            .sum::<BigUint>();
+        assert_eq!(solve_part1(&input)?, expected);
+
+        // 39 ones repeat blocks of 1, 3 and 13 digits but count once
+        let input = input_generator("111111111111111111111111111111111111111-111111111111111111111111111111111111111")?;
+        assert_eq!(solve_part2(&input)?, BigUint::from(111111111111111111111111111111111111111u128));
+        assert_eq!(solve_part1(&input)?, BigUint::ZERO);
+
+        Ok(())
+    }
+
+    fn classify_decimal(id: u128) -> Structure {
+        classify(id, Radix::DECIMAL)
+    }
+
+    #[test]
+    fn classifies() {
+        assert_eq!(classify_decimal(123123), Structure { block: 123, repeats: vec![1, 2] });
+        assert_eq!(classify_decimal(111111), Structure { block: 1, repeats: vec![1, 2, 3, 6] });
+        assert_eq!(classify_decimal(121212), Structure { block: 12, repeats: vec![1, 3] });
+        assert_eq!(classify_decimal(7), Structure { block: 7, repeats: vec![1] });
+        assert_eq!(classify_decimal(0), Structure { block: 0, repeats: vec![1] });
+        assert_eq!(classify_decimal(u128::MAX), Structure { block: u128::MAX, repeats: vec![1] });
+
+        assert!(classify_decimal(123123).is_invalid() && classify_decimal(123123).is_invalid2());
+        assert!(!classify_decimal(121212).is_invalid() && classify_decimal(121212).is_invalid2());
+        assert!(!classify_decimal(1234).is_invalid2());
+    }
+
+    #[test]
+    fn notes() -> Result<()> {
+        let input = input_generator("95-115,1111-1111,1-9")?;
+
+        let notes = Notes::default();
+        solve_part1_with(&input, &Params::default(), &Cancel::default(), &NoProgress, &notes)?;
+        assert_eq!(
+            notes.into_lines(),
+            vec!["1-9: 0 invalid", "95-115: 1 invalid (1 repeating 2x)", "1111-1111: 1 invalid (1 repeating 4x)"]
+        );
+
+        let notes = Notes::default();
+        solve_part2_with(&input, &Params::default(), &Cancel::default(), &NoProgress, &notes)?;
+        assert_eq!(
+            notes.into_lines(),
+            vec![
+                "1-9: 0 invalid",
+                "95-115: 2 invalid (1 repeating 2x, 1 repeating 3x)",
+                "1111-1111: 1 invalid (1 repeating 4x)"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn overlapping() -> Result<()> {
+        let input = input_generator("11-22,15-30,11-22,40-33")?;
+        assert_eq!(
+            issues(&input),
+            vec![Issue::Reversed(3), Issue::Overlap(0, 1), Issue::Overlap(0, 2), Issue::Overlap(1, 2)]
+        );
+        assert_eq!(
+            normalize(&input, Policy::Merge, Radix::DECIMAL)?,
+            vec![Range { start: 11, end: 30 }, Range { start: 33, end: 40 }]
+        );
+
+        // 11, 22 and 33 count once however often they are listed
+        let notes = Notes::default();
+        let sum = solve_part1_with(&input, &Params::default(), &Cancel::default(), &NoProgress, &notes)?;
+        assert_eq!(sum, BigUint::from(11u32 + 22 + 33));
+        assert_eq!(
+            notes.into_lines(),
+            vec![
+                "range 4 (40-33) is reversed",
+                "ranges 1 (11-22) and 2 (15-30) overlap",
+                "ranges 1 (11-22) and 3 (11-22) overlap",
+                "ranges 2 (15-30) and 3 (11-22) overlap",
+                "11-30: 2 invalid (2 repeating 2x)",
+                "33-40: 1 invalid (1 repeating 2x)"
+            ]
+        );
+
+        // touching ranges share no ID
+        assert!(issues(&input_generator("11-22,23-30,5-5")?).is_empty());
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn rejects() -> Result<()> {
+        let params = Params::with_overrides(&[("ranges".to_string(), "reject".to_string())])?;
+        let (cancel, notes) = (Cancel::default(), Notes::default());
+
+        let err = solve_part2_with(&input_generator("11-22,11-22")?, &params, &cancel, &NoProgress, &notes).unwrap_err();
+        assert!(format!("{:#}", err).contains("ranges 1 (11-22) and 2 (11-22) overlap"));
+        let err = solve_part2_with(&input_generator("22-11")?, &params, &cancel, &NoProgress, &notes).unwrap_err();
+        assert!(format!("{:#}", err).contains("range 1 (22-11) is reversed"));
+
+        // disjoint ranges are solved as given
+        assert_eq!(solve_part1_with(&input()?, &params, &cancel, &NoProgress, &notes)?, BigUint::from(1227775554u64));
+
+        // bounds are named the way the input writes them
+        let params = Params { radix: Radix::new(16)?, ..params };
+        let input = input_generator_with("a-14,10-1e", &params)?;
+        let err = solve_part1_with(&input, &params, &cancel, &NoProgress, &notes).unwrap_err();
+        assert!(format!("{:#}", err).contains("ranges 1 (a-14) and 2 (10-1e) overlap"));
+
+        assert!(Params::with_overrides(&[("ranges".to_string(), "sort".to_string())]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn radixes() -> Result<()> {
+        let (cancel, ignored) = (Cancel::default(), Notes::default());
+        let with_radix = |radix: &str| Params::with_overrides(&[("radix".to_string(), radix.to_string())]);
+
+        // 11, 1010 and 1111 repeat twice, 111 three times
+        let (binary, params) = (Radix::new(2)?, with_radix("2")?);
+        let input = input_generator_with("0-1111", &params)?;
+        assert_eq!(solve_part1_with(&input, &params, &cancel, &NoProgress, &ignored)?, BigUint::from(3u32 + 10 + 15));
+        assert_eq!(solve_part2_with(&input, &params, &cancel, &NoProgress, &ignored)?, BigUint::from(3u32 + 7 + 10 + 15));
+
+        // 0x11 to 0xff with two equal digits
+        let (hex, params) = (Radix::new(16)?, with_radix("16")?);
+        let input = input_generator_with("10-FF", &params)?;
+        let notes = Notes::default();
+        assert_eq!(solve_part1_with(&input, &params, &cancel, &NoProgress, &notes)?, BigUint::from(17u32 * 120));
+        assert_eq!(notes.into_lines(), vec!["10-ff: 15 invalid (15 repeating 2x)"]);
+
+        assert_eq!(classify(0xabab, hex), Structure { block: 0xab, repeats: vec![1, 2] });
+        assert_eq!(classify(0b101101, binary), Structure { block: 0b101, repeats: vec![1, 2] });
+        assert_eq!(Radix::new(36)?.format(36 * 36 - 1), "zz");
+        assert_eq!(binary.max_len(), 128);
+
+        assert!(Radix::new(1).is_err());
+        assert!(Radix::new(37).is_err());
+        assert_eq!(with_radix("36")?.radix, Radix::new(36)?);
+        for radix in ["0", "1", "37", "x"] {
+            assert!(with_radix(radix).is_err(), "radix {}", radix);
+        }
+        assert!(parse_ranges("10-12", binary).is_err());
+        assert!(parse_ranges("1g-2a", hex).is_err());
+
+        Ok(())
+    }
+
+    fn is_invalid(id: u128, div: usize) -> bool {
+        let s = id.to_string();
+        s.len().is_multiple_of(div) && s.as_bytes().chunks(s.len() / div).all(|c| c == &s.as_bytes()[..s.len() / div])
+    }
+
+    proptest! {
+        #[test]
+        fn matches_scanning(start in 0u128..2_000_000, len in 0u128..3000) {
+            let range = Range { start, end: start + len };
+            let scan = |f: &dyn Fn(u128) -> bool| (range.start..=range.end).filter(|id| f(*id)).map(BigUint::from).sum::<BigUint>();
+
+            prop_assert_eq!(sum_twice(&range, Radix::DECIMAL), scan(&|id| is_invalid(id, 2)));
+            prop_assert_eq!(sum_repeated(&range, Radix::DECIMAL), scan(&|id| (2..=id.to_string().len()).any(|d| is_invalid(id, d))));
+        }
+
+        #[test]
+        fn radixes_match_scanning(radix in 2u32..=36, start in 0u128..200_000, len in 0u128..2000) {
+            let radix = Radix::new(radix).unwrap();
+            let range = Range { start, end: start + len };
+            let blocks = |id: u128| {
+                let s = radix.format(id);
+                (1..=s.len()).filter(|d| s.len().is_multiple_of(*d) && s == s[..s.len() / d].repeat(*d)).collect::<Vec<_>>()
+            };
+            let scan = |f: &dyn Fn(Vec<usize>) -> bool| (range.start..=range.end).filter(|id| f(blocks(*id))).map(BigUint::from).sum::<BigUint>();
+
+            prop_assert_eq!(sum_twice(&range, radix), scan(&|d| d.contains(&2)));
+            prop_assert_eq!(sum_repeated(&range, radix), scan(&|d| d.len() > 1));
+        }
+
+        #[test]
+        fn classification_matches(id in any::<u32>(), repeats in 1usize..4) {
+            let id = id.to_string().repeat(repeats).parse::<u128>().unwrap();
+            let structure = classify_decimal(id);
+
+            prop_assert_eq!(structure.is_invalid(), is_invalid(id, 2));
+            for r in 1..=Radix::DECIMAL.digits(id) {
+                prop_assert_eq!(structure.repeats.contains(&r), is_invalid(id, r as usize));
+            }
+        }
+
+        #[test]
+        fn repeat_counts_match_classification(start in 0u128..2_000_000, len in 0u128..3000) {
+            let range = Range { start, end: start + len };
+            let mut expected = BTreeMap::new();
+            for structure in (start..=start + len).map(classify_decimal).filter(Structure::is_invalid2) {
+                *expected.entry(structure.repeats[structure.repeats.len() - 1]).or_insert(0) += 1;
+            }
+
+            prop_assert_eq!(repeat_counts(&range, Radix::DECIMAL), expected);
+        }
+    }
+}
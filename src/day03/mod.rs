@@ -1,22 +1,66 @@
-use anyhow::Result;
+use std::io::BufRead;
+
+use anyhow::{Context, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::utils::{AocError, stream_lines};
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Number of batteries to turn on in each bank for part 2.
+    pub digits: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { digits: 12 }
+    }
+}
+
+impl crate::utils::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "digits" => {
+                self.digits = value.parse()?;
+                if self.digits == 0 {
+                    Err(AocError::GenericError).context("digits has to be at least 1")?;
+                }
+            }
+            _ => Err(AocError::UnknownParameter(key.to_string()))?,
+        }
+        Ok(())
+    }
+}
+
 #[aoc_generator(day03)]
 pub fn input_generator(input: &str) -> Result<Vec<Vec<u128>>> {
     let result = input
         .lines()
         .filter(|l| !l.is_empty())
-        .map(|l| {
-            l.chars()
-                .filter_map(|c| c.to_digit(10))
-                .map(|d| d as u128)
-                .collect::<Vec<_>>()
-        })
+        .map(parse_bank)
         .filter(|v| !v.is_empty())
         .collect::<Vec<_>>();
     Ok(result)
 }
 
+fn parse_bank(l: &str) -> Vec<u128> {
+    l.chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| d as u128)
+        .collect::<Vec<_>>()
+}
+
+/// Sums `joltage` over the banks in `reader`, one line at a time.
+fn sum_stream(reader: impl BufRead, joltage: impl Fn(&[u128]) -> Result<u128>) -> Result<u128> {
+    stream_lines(reader).try_fold(0, |sum, l| {
+        let bank = parse_bank(&l?);
+        if bank.is_empty() {
+            return Ok(sum);
+        }
+        Ok(sum + joltage(&bank)?)
+    })
+}
+
 fn find_largest_naive(battery: &[u128]) -> Result<u128> {
     let l = battery.len();
     let mut max = 0;
@@ -44,18 +88,25 @@ pub fn solve_part1(input: &[Vec<u128>]) -> Result<u128> {
     Ok(result)
 }
 
+pub fn solve_part1_stream(reader: impl BufRead) -> Result<u128> {
+    sum_stream(reader, find_largest_naive)
+}
+
 fn find_largest(battery: &[u128], len: usize) -> Result<u128> {
     let l = battery.len();
+    if len > l {
+        Err(AocError::GenericError).context(format!("Can't turn on {} of {} batteries", len, l))?;
+    }
+
     let mut num = vec![];
     let mut last_index = 0;
 
-
     for i in 0..len {
         let mut max = 0;
         let mut index_of_max = 0;
-        for j in last_index..l - (len - 1) + i {
-            if battery[j] > max {
-                max = battery[j];
+        for (j, &b) in battery.iter().enumerate().take(l - (len - 1) + i).skip(last_index) {
+            if b > max {
+                max = b;
                 index_of_max = j;
             }
         }
@@ -71,17 +122,27 @@ fn find_largest(battery: &[u128], len: usize) -> Result<u128> {
 
 #[aoc(day03, part2)]
 pub fn solve_part2(input: &[Vec<u128>]) -> Result<u128> {
+    solve_part2_with(input, &Params::default())
+}
+
+pub fn solve_part2_with(input: &[Vec<u128>], params: &Params) -> Result<u128> {
     Ok(input
         .iter()
-        .map(|b| find_largest(b, 12))
+        .map(|b| find_largest(b, params.digits))
         .collect::<Result<Vec<_>>>()?
         .into_iter()
         .sum::<u128>())
 }
 
+pub fn solve_part2_stream(reader: impl BufRead, params: &Params) -> Result<u128> {
+    sum_stream(reader, |b| find_largest(b, params.digits))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
 
     fn input() -> Result<Vec<Vec<u128>>> {
         let input = "987654321111111
@@ -105,4 +166,52 @@ mod test {
         assert_eq!(solve_part2(&input)?, 3121910778619);
         Ok(())
     }
+
+    #[test]
+    fn stream() -> Result<()> {
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111\n";
+        assert_eq!(solve_part1_stream(input.as_bytes())?, 357);
+        assert_eq!(solve_part2_stream(input.as_bytes(), &Params::default())?, 3121910778619);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_with_two_digits() -> Result<()> {
+        let input = input()?;
+        assert_eq!(solve_part2_with(&input, &Params { digits: 2 })?, 357);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_bad_digits() -> Result<()> {
+        use crate::utils::Params as _;
+
+        let err = Params::with_overrides(&[("digits".to_string(), "0".to_string())]).unwrap_err();
+        assert!(format!("{:#}", err).contains("at least 1"));
+
+        let err = solve_part2_with(&input()?, &Params { digits: 20 }).unwrap_err();
+        assert!(format!("{:#}", err).contains("Can't turn on 20 of 15 batteries"));
+        assert!(solve_part2_stream("12
+".as_bytes(), &Params { digits: 3 }).is_err());
+        Ok(())
+    }
+
+    fn brute_force(battery: &[u128], len: usize) -> u128 {
+        battery
+            .iter()
+            .combinations(len)
+            .map(|c| c.into_iter().fold(0, |num, d| num * 10 + d))
+            .max()
+            .unwrap_or(0)
+    }
+
+    proptest! {
+        #[test]
+        fn find_largest_picks_best_subsequence(
+            (battery, len) in prop::collection::vec(1u128..10, 1..10)
+                .prop_flat_map(|b| { let l = b.len(); (Just(b), 1..=l) })
+        ) {
+            prop_assert_eq!(find_largest(&battery, len).unwrap(), brute_force(&battery, len));
+        }
+    }
 }
@@ -1,8 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::viz::{Color, Frame};
+
 type Coords = (i32, i32);
 type Map = HashMap<Coords, char>;
 
@@ -87,9 +89,67 @@ pub fn solve_part2(input: &Map) -> Result<usize> {
     Ok(initial_count - last_count)
 }
 
+/// Rolls removed in each round when every accessible roll of a round is taken
+/// away at once, sorted by position.
+pub fn removal_waves(input: &Map) -> Vec<Vec<Coords>> {
+    let mut map = input.clone();
+    let mut waves = vec![];
+
+    loop {
+        let mut wave = map
+            .iter()
+            .filter(|(pos, c)| **c == '@' && count_neighbors(&map, **pos) < 4)
+            .map(|(pos, _)| *pos)
+            .collect::<Vec<_>>();
+        if wave.is_empty() {
+            break;
+        }
+
+        wave.sort();
+        for pos in &wave {
+            map.insert(*pos, '.');
+        }
+        waves.push(wave);
+    }
+
+    waves
+}
+
+/// One frame per removal wave. Rolls of the current wave are drawn as `x`,
+/// the ones removed before as `.`.
+pub fn render(input: &Map) -> Result<Vec<Frame>> {
+    let height = input.keys().map(|p| p.0 + 1).max().unwrap_or(0) as i64;
+    let width = input.keys().map(|p| p.1 + 1).max().unwrap_or(0) as i64;
+    let waves = removal_waves(input);
+    let mut removed = HashSet::new();
+
+    let mut frames = vec![];
+    for (i, wave) in waves.iter().enumerate() {
+        let mut frame = Frame::new(format!("wave {}: {} rolls removed", i + 1, wave.len()), width, height);
+        for pos in input.iter().filter(|(_, c)| **c == '@').map(|(pos, _)| pos) {
+            let (glyph, color) = if removed.contains(pos) {
+                ('.', Color::Dim)
+            } else if wave.binary_search(pos).is_ok() {
+                ('x', Color::Highlight)
+            } else {
+                ('@', Color::Primary)
+            };
+            frame.cell(pos.1 as i64, pos.0 as i64, glyph, color);
+        }
+        frames.push(frame);
+
+        removed.extend(wave.iter().cloned());
+    }
+
+    Ok(frames)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
+
+    use crate::{snapshot::compare_snapshot, viz::Backend};
 
     fn input() -> Result<Map> {
         let input = "..@@.@@@@.
@@ -119,4 +179,43 @@ mod test {
 
         Ok(assert_eq!(solve_part2(&input)?, 43))
     }
+
+    #[test]
+    fn waves() -> Result<()> {
+        let input = input()?;
+        let waves = removal_waves(&input);
+
+        assert_eq!(waves[0].len(), solve_part1(&input)?);
+        assert_eq!(waves.iter().map(|w| w.len()).sum::<usize>(), 43);
+        assert_eq!(render(&input)?.len(), waves.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn waves_snapshot() -> Result<()> {
+        let frames = render(&input()?)?;
+        compare_snapshot("day04_waves", &Backend::Text.render(&frames))
+    }
+
+    fn grid() -> impl Strategy<Value = String> {
+        (1usize..12, 1usize..12).prop_flat_map(|(w, h)| {
+            prop::collection::vec(prop::bool::weighted(0.6), w * h).prop_map(move |cells| {
+                cells
+                    .chunks(w)
+                    .map(|row| row.iter().map(|c| if *c { '@' } else { '.' }).collect::<String>())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn repeated_removal_removes_at_least_first_wave(grid in grid()) {
+            let map = input_generator(&grid).unwrap();
+
+            prop_assert!(solve_part2(&map).unwrap() >= solve_part1(&map).unwrap());
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use std::{cmp::max, str::FromStr};
+use std::{cmp::max, io::BufRead, str::FromStr};
 
 use anyhow::{Context, Error, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
@@ -7,8 +7,8 @@ use crate::utils::AocError;
 
 #[derive(Debug, Clone)]
 pub struct Range {
-    start: u128,
-    end: u128,
+    pub(crate) start: u128,
+    pub(crate) end: u128,
 }
 
 impl FromStr for Range {
@@ -69,11 +69,37 @@ pub fn solve_part1(input: &(Vec<Range>, Vec<u128>)) -> Result<usize> {
         .count())
 }
 
+/// Part 1 with the ranges held in memory while the ingredient IDs are read
+/// one line at a time.
+pub fn solve_part1_stream(reader: impl BufRead) -> Result<usize> {
+    let mut lines = reader.lines();
+    let mut ranges = vec![];
+
+    for line in lines.by_ref() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+        ranges.push(Range::from_str(&line)?);
+    }
+
+    lines.try_fold(0, |count, line| {
+        let line = line?;
+        if line.is_empty() {
+            return Ok(count);
+        }
+        let value = line.parse::<u128>()?;
+        Ok(count + usize::from(is_in_any(&ranges, value)))
+    })
+}
+
 fn merge(ranges: &[Range]) -> Vec<Range> {
     let mut ranges = ranges.to_vec();
-    ranges.sort_by(|a, b| a.start.cmp(&b.start));
+    ranges.sort_by_key(|r| r.start);
 
-    let mut current = ranges[0].clone();
+    let Some(mut current) = ranges.first().cloned() else {
+        return vec![];
+    };
     let mut merged = vec![];
 
     ranges.iter().skip(1).for_each(|range| {
@@ -100,6 +126,7 @@ pub fn solve_part2(input: &(Vec<Range>, Vec<u128>)) -> Result<u128> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
 
     fn input() -> Result<(Vec<Range>, Vec<u128>)> {
         let input = "3-5
@@ -123,9 +150,37 @@ mod test {
         Ok(assert_eq!(solve_part1(&input)?, 3))
     }
 
+    #[test]
+    fn part1_stream() -> Result<()> {
+        let input = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n";
+        Ok(assert_eq!(solve_part1_stream(input.as_bytes())?, 3))
+    }
+
     #[test]
     fn part2() -> Result<()> {
         let input = input()?;
         Ok(assert_eq!(solve_part2(&input)?, 14))
     }
+
+    fn ranges() -> impl Strategy<Value = Vec<Range>> {
+        prop::collection::vec((0u128..200, 0u128..20), 1..20).prop_map(|v| {
+            v.into_iter()
+                .map(|(start, len)| Range { start, end: start + len })
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn merge_is_sorted_disjoint_and_covers_input(ranges in ranges()) {
+            let merged = merge(&ranges);
+
+            for pair in merged.windows(2) {
+                prop_assert!(pair[0].end < pair[1].start);
+            }
+            for v in 0..=220 {
+                prop_assert_eq!(is_in_any(&ranges, v), is_in_any(&merged, v));
+            }
+        }
+    }
 }
@@ -1,7 +1,7 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::utils::AocError;
+use crate::utils::{AocError, Diagnostic};
 
 #[derive(Debug)]
 pub enum Op {
@@ -28,18 +28,18 @@ impl Op {
         })
     }
 
-    fn calculate(&self) -> i128 {
+    fn calculate(&self) -> Result<i128> {
         use Op::*;
 
         let result = match self {
-            Add(v) => v.iter().sum::<i128>(),
-            Mul(v) => v.iter().product::<i128>(),
+            Add(v) => v.iter().try_fold(0i128, |acc, x| acc.checked_add(*x)),
+            Mul(v) => v.iter().try_fold(1i128, |acc, x| acc.checked_mul(*x)),
         };
 
-        result
+        Ok(result.ok_or(AocError::Overflow)?)
     }
 
-    fn from_part2(cols: &Vec<Vec<char>>, op: &str) -> Result<Op> {
+    fn from_part2(cols: &[Vec<char>], op: &str) -> Result<Op> {
         use Op::*;
 
         let nums = cols.iter().map(|c| Ok(c.iter().collect::<String>().trim().parse::<i128>()?)).collect::<Result<Vec<_>>>()?;
@@ -57,6 +57,45 @@ pub fn input_generator(input: &str) -> Result<String> {
     Ok(input.to_string())
 }
 
+/// Every row needs a number for each operator, and operators are `+` or `*`.
+pub fn validate(input: &str) -> Vec<Diagnostic> {
+    let mut rows = input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.split_whitespace().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let Some(ops) = rows.pop() else {
+        return vec![Diagnostic::new("input", "is empty")];
+    };
+
+    let mut diagnostics = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| **op != "+" && **op != "*")
+        .map(|(i, op)| Diagnostic::new(format!("column {}", i + 1), format!("unknown operator '{}'", op)))
+        .collect::<Vec<_>>();
+
+    for (i, row) in rows.iter().enumerate() {
+        if row.len() != ops.len() {
+            diagnostics.push(Diagnostic::new(
+                format!("row {}", i + 1),
+                format!("has {} numbers for {} operators", row.len(), ops.len()),
+            ));
+        }
+        for (j, value) in row.iter().enumerate() {
+            if value.parse::<i128>().is_err() {
+                diagnostics.push(Diagnostic::new(
+                    format!("row {}, column {}", i + 1, j + 1),
+                    format!("'{}' is not a number", value),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
 fn parse_part1(input: &str) -> Result<Vec<Op>> {
     let input = input
         .lines()
@@ -64,15 +103,23 @@ fn parse_part1(input: &str) -> Result<Vec<Op>> {
         .map(|l| l.split(' ').filter(|c| !c.is_empty()).collect::<Vec<_>>())
         .collect::<Vec<_>>();
 
-    let num_cols = input[0].len();
+    let num_cols = input.first().map_or(0, |row| row.len());
+
+    Ok((0..num_cols).filter_map(|c| Op::from_column(&input, c)).collect::<Vec<_>>())
+}
 
-    Ok((0..num_cols).into_iter().filter_map(|c| Op::from_column(&input, c)).collect::<Vec<_>>())
+fn total(ops: &[Op]) -> Result<i128> {
+    ops.iter().try_fold(0i128, |acc, o| {
+        acc.checked_add(o.calculate()?)
+            .ok_or(AocError::Overflow)
+            .context("Sum of all problems overflows")
+    })
 }
 
 #[aoc(day06, part1)]
 pub fn solve_part1(input: &str) -> Result<i128> {
     let input = parse_part1(input)?;
-    Ok(input.iter().map(|o| o.calculate()).sum::<i128>())
+    total(&input)
 }
 
 fn parse_part2(input: &str) -> Result<Vec<Op>> {
@@ -93,7 +140,7 @@ fn parse_part2(input: &str) -> Result<Vec<Op>> {
             col.push(col_line);
         }
     }
-    if col.len() != 0 {
+    if !col.is_empty() {
         cols.push(col);
     }
 
@@ -110,7 +157,7 @@ fn parse_part2(input: &str) -> Result<Vec<Op>> {
 #[aoc(day06, part2)]
 pub fn solve_part2(input: &str) -> Result<i128> {
     let input = parse_part2(input)?;
-    Ok(input.iter().map(|o| o.calculate()).sum::<i128>())
+    total(&input)
 }
 
 #[cfg(test)]
@@ -136,4 +183,32 @@ mod test {
         let input = input();
         Ok(assert_eq!(solve_part2(&input)?, 3263827))
     }
+
+    #[test]
+    fn validation() {
+        assert!(validate(&input()).is_empty());
+
+        let messages = validate("1 2 3\n4 x\n* - +\n")
+            .into_iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "column 2: unknown operator '-'",
+                "row 2: has 2 numbers for 3 operators",
+                "row 2, column 2: 'x' is not a number",
+            ]
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let input = "99999999999999999999 99999999999999999999
+99999999999999999999 1
+*                    +
+";
+        let err = solve_part1(input).unwrap_err();
+        assert!(matches!(err.downcast_ref::<AocError>(), Some(AocError::Overflow)));
+    }
 }
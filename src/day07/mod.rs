@@ -2,9 +2,11 @@ use std::{collections::{HashMap, HashSet}};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 use anyhow::{Result, Context};
-use memoize::memoize;
 
-use crate::utils::AocError;
+use crate::{
+    utils::{AocError, BigUint},
+    viz::{Color, Frame},
+};
 
 type Coords = (i32, i32);
 type Map = HashMap<Coords, char>;
@@ -29,10 +31,10 @@ fn get_start(map: &Map) -> Option<i32> {
 }
 
 fn get_num_layers(map: &Map) -> Option<i32> {
-    map.iter().map(|(c, _)| c.0).max()
+    map.keys().map(|c| c.0).max()
 }
 
-fn split_beams(map: &Map, layer: i32, beams: &Vec<i32>) -> (i32, Vec<i32>) {
+fn split_beams(map: &Map, layer: i32, beams: &[i32]) -> (i32, Vec<i32>) {
     let splitters = map.iter().filter(|(c, v)| c.0 == layer && beams.contains(&c.1) && **v == '^').map(|(c, _)| c.1).collect::<Vec<_>>();
     let num_new_splits = splitters.len();
     let continuing_beams = beams.iter().filter(|p| !splitters.contains(p)).cloned().collect::<HashSet<_>>();
@@ -50,7 +52,7 @@ pub fn solve_part1(input: &Map) -> Result<i32> {
     let mut beams = vec![start];
 
     for l in 1..layers {
-        let (s, new_beams) = split_beams(&input, l, &beams);
+        let (s, new_beams) = split_beams(input, l, &beams);
         splits += s;
         beams = new_beams;
     }
@@ -58,31 +60,72 @@ pub fn solve_part1(input: &Map) -> Result<i32> {
     Ok(splits)
 }
 
-#[memoize(Ignore: map, Ignore: max)]
-fn split_beams3(map: &Map, max: i32, row: i32, col: i32) -> u128 {
+/// Sorted columns of the beams leaving each layer, starting with the layer of `S`.
+pub fn beam_layers(input: &Map) -> Result<Vec<Vec<i32>>> {
+    let start = get_start(input).ok_or(AocError::GenericError).context("Could not find start")?;
+    let layers= get_num_layers(input).ok_or(AocError::GenericError).context("Could not count layers")?;
+    let mut result = vec![vec![start]];
+
+    for l in 1..layers {
+        let (_, mut beams) = split_beams(input, l, &result[result.len() - 1]);
+        beams.sort();
+        result.push(beams);
+    }
+
+    Ok(result)
+}
+
+/// The manifold with the beams of part 1 drawn in.
+pub fn render(input: &Map) -> Result<Vec<Frame>> {
+    let height = input.keys().map(|p| p.0 + 1).max().unwrap_or(0) as i64;
+    let width = input.keys().map(|p| p.1 + 1).max().unwrap_or(0) as i64;
+    let mut frame = Frame::new(format!("{} beam splits", solve_part1(input)?), width, height);
+
+    for (row, beams) in beam_layers(input)?.iter().enumerate() {
+        for col in beams {
+            frame.cell(*col as i64, row as i64, '|', Color::Accent(0));
+        }
+    }
+    for (pos, c) in input.iter().filter(|(_, c)| **c != '.') {
+        let color = if *c == 'S' { Color::Highlight } else { Color::Primary };
+        frame.cell(pos.1 as i64, pos.0 as i64, *c, color);
+    }
+
+    Ok(vec![frame])
+}
+
+/// Number of timelines of a particle entering at `row`, `col`. Paths merge a
+/// lot, so the counts of every visited tile are kept in `cache`.
+fn split_beams3(map: &Map, max: i32, row: i32, col: i32, cache: &mut HashMap<Coords, BigUint>) -> BigUint {
     if row >= max {
-        return 1;
+        return BigUint::from(1u32);
+    }
+    if let Some(count) = cache.get(&(row, col)) {
+        return count.clone();
     }
 
-    let result = match map.get(&(row, col)) {
-        Some('^') => split_beams3(map, max, row+1, col - 1) + split_beams3(map, max, row+1, col + 1),
-        _ => split_beams3(map, max, row+1, col),
+    let count = match map.get(&(row, col)) {
+        Some('^') => split_beams3(map, max, row+1, col - 1, cache) + split_beams3(map, max, row+1, col + 1, cache),
+        _ => split_beams3(map, max, row+1, col, cache),
     };
-
-    result
+    cache.insert((row, col), count.clone());
+    count
 }
 
 #[aoc(day07, part2)]
-pub fn solve_part2(input: &Map) -> Result<u128> {
+pub fn solve_part2(input: &Map) -> Result<BigUint> {
     let start = get_start(input).ok_or(AocError::GenericError).context("Could not find start")?;
     let layers= get_num_layers(input).ok_or(AocError::GenericError).context("Could not count layers")?;
 
-    Ok(split_beams3(&input, layers, 1, start))
+    Ok(split_beams3(input, layers, 1, start, &mut HashMap::new()))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use itertools::Itertools;
+
+    use crate::{snapshot::compare_snapshot, viz::Backend};
 
     fn input() -> Result<Map> {
     let input = ".......S.......
@@ -114,6 +157,31 @@ mod test {
     #[test]
     fn part2() -> Result<()> {
         let input = input()?;
-        Ok(assert_eq!(solve_part2(&input)?, 40))
+        Ok(assert_eq!(solve_part2(&input)?, BigUint::from(40u32)))
+    }
+
+    #[test]
+    fn beams() -> Result<()> {
+        let layers = beam_layers(&input()?)?;
+
+        assert_eq!(layers.len(), 15);
+        assert_eq!(layers[0], vec![7]);
+        assert_eq!(layers[2], vec![6, 8]);
+        assert_eq!(layers[4], vec![5, 7, 9]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn beams_snapshot() -> Result<()> {
+        let layers = beam_layers(&input()?)?
+            .iter()
+            .enumerate()
+            .map(|(i, beams)| format!("{:2}: {}\n", i, beams.iter().join(" ")))
+            .collect::<String>();
+        compare_snapshot("day07_beam_layers", &layers)?;
+
+        let frames = render(&input()?)?;
+        compare_snapshot("day07_manifold", &Backend::Text.render(&frames))
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use std::{
     collections::{HashSet, VecDeque},
+    io::BufRead,
     str::FromStr,
 };
 
@@ -7,10 +8,10 @@ use anyhow::{Context, Error, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 
-use crate::utils::AocError;
+use crate::utils::{AocError, NoProgress, Progress, stream_lines};
 
 pub type Base = i64;
-pub struct Coords(Base, Base, Base);
+pub struct Coords(pub(crate) Base, pub(crate) Base, pub(crate) Base);
 
 impl FromStr for Coords {
     type Err = Error;
@@ -29,6 +30,28 @@ impl FromStr for Coords {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Number of shortest connections to make in part 1.
+    pub connections: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { connections: 1000 }
+    }
+}
+
+impl crate::utils::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "connections" => self.connections = value.parse()?,
+            _ => Err(AocError::UnknownParameter(key.to_string()))?,
+        }
+        Ok(())
+    }
+}
+
 #[aoc_generator(day08)]
 pub fn input_generator(input: &str) -> Result<Vec<Coords>> {
     input
@@ -38,6 +61,14 @@ pub fn input_generator(input: &str) -> Result<Vec<Coords>> {
         .collect::<Result<Vec<_>>>()
 }
 
+/// Reads the junction boxes one line at a time, so only the parsed
+/// coordinates are held in memory, not the text.
+pub fn input_generator_stream(reader: impl BufRead) -> Result<Vec<Coords>> {
+    stream_lines(reader)
+        .map(|l| Coords::from_str(&l?))
+        .collect::<Result<Vec<_>>>()
+}
+
 fn distance(a: &Coords, b: &Coords) -> f64 {
     f64::sqrt(((b.0 - a.0).pow(2) + (b.1 - a.1).pow(2) + (b.2 - a.2).pow(2)) as f64)
 }
@@ -73,11 +104,11 @@ fn disjunct_networks(paths: &[(usize, usize)]) -> Vec<Vec<usize>> {
 
 #[aoc(day08, part1)]
 pub fn solve_part1(input: &[Coords]) -> Result<usize> {
-    let num = if input.len() < 50 {
-        10
-    } else {
-        1000
-    };
+    solve_part1_with(input, &Params::default())
+}
+
+pub fn solve_part1_with(input: &[Coords], params: &Params) -> Result<usize> {
+    let num = params.connections;
     let mut pairs = vec![];
     let len = input.len();
 
@@ -101,12 +132,20 @@ pub fn solve_part1(input: &[Coords]) -> Result<usize> {
     Ok(networks.into_iter().rev().take(3).map(|n| n.len()).product())
 }
 
-fn find_last_connection(pairs: VecDeque<(usize, usize)>, max: usize) -> Option<(usize, usize)> {
+fn find_last_connection(
+    pairs: VecDeque<(usize, usize)>,
+    max: usize,
+    progress: &dyn Progress,
+) -> Option<(usize, usize)> {
+    // the answer is usually found long before all pairs are connected, so
+    // progress counts the merges of networks, max - 1 of them join everything
+    let total = max.saturating_sub(1) as u64;
     let mut connections = pairs.iter().take(max).cloned().collect::<Vec<_>>();
     let mut pairs = pairs.into_iter().skip(max).collect::<VecDeque<_>>();
     while let Some(first) = pairs.pop_front() {
         connections.push((first.0, first.1));
         let mut networks = disjunct_networks(&connections);
+        progress.report(networks.iter().map(|n| n.len() as u64 - 1).sum(), total);
         networks.sort_by_key(|a| a.len());
 
         if networks[0].len() == max {
@@ -119,6 +158,10 @@ fn find_last_connection(pairs: VecDeque<(usize, usize)>, max: usize) -> Option<(
 
 #[aoc(day08, part2)]
 pub fn solve_part2(input: &[Coords]) -> Result<Base> {
+    solve_part2_with(input, &NoProgress)
+}
+
+pub fn solve_part2_with(input: &[Coords], progress: &dyn Progress) -> Result<Base> {
     let mut pairs = vec![];
     let len = input.len();
 
@@ -137,7 +180,7 @@ pub fn solve_part2(input: &[Coords]) -> Result<Base> {
     });
     let pairs = pairs.into_iter().map(|p| (p.0, p.1)).collect::<VecDeque<_>>();
 
-    if let Some(last) = find_last_connection(pairs, input.len()) {
+    if let Some(last) = find_last_connection(pairs, input.len(), progress) {
         Ok(input[last.0].0 * input[last.1].0)
     } else {
         Err(AocError::GenericError).context("Could not connect all junction boxes")
@@ -176,7 +219,7 @@ mod test {
     #[test]
     fn part1() -> Result<()> {
         let input = input()?;
-        Ok(assert_eq!(solve_part1(&input)?, 40))
+        Ok(assert_eq!(solve_part1_with(&input, &Params { connections: 10 })?, 40))
     }
 
     #[test]
@@ -184,4 +227,30 @@ mod test {
         let input = input()?;
         Ok(assert_eq!(solve_part2(&input)?, 25272))
     }
+
+    #[test]
+    fn progress() -> Result<()> {
+        struct Reports(std::sync::Mutex<Vec<(u64, u64)>>);
+        impl Progress for Reports {
+            fn report(&self, done: u64, total: u64) {
+                self.0.lock().unwrap().push((done, total));
+            }
+        }
+
+        let input = input()?;
+        let reports = Reports(Default::default());
+        solve_part2_with(&input, &reports)?;
+
+        // the last connection joins the 20 boxes, one merge after another
+        let reports = reports.0.into_inner().unwrap();
+        assert!(reports.windows(2).all(|w| w[0].0 <= w[1].0));
+        Ok(assert_eq!(reports.last(), Some(&(19, 19))))
+    }
+
+    #[test]
+    fn stream() -> Result<()> {
+        let input = "162,817,812\n57,618,57\n\n906,360,560\n";
+        let coords = input_generator_stream(input.as_bytes())?;
+        Ok(assert_eq!(coords.len(), 3))
+    }
 }
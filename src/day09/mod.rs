@@ -3,12 +3,15 @@ use std::str::FromStr;
 use aoc_runner_derive::{aoc, aoc_generator};
 use anyhow::{Context, Error, Result};
 
-use crate::utils::AocError;
+use crate::{
+    utils::{AocError, Diagnostic},
+    viz::{Color, Frame},
+};
 
 
 pub type Base = i64;
 #[derive(Debug, Clone)]
-pub struct Coords(Base, Base);
+pub struct Coords(pub(crate) Base, pub(crate) Base);
 
 impl FromStr for Coords {
     type Err = Error;
@@ -30,35 +33,73 @@ impl FromStr for Coords {
 
 #[aoc_generator(day09)]
 pub fn input_generator(input: &str) -> Result<Vec<Coords>> {
-    let input = "7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3
-";
-
     input.lines().filter(|l| !l.is_empty()).map(Coords::from_str).collect::<Result<Vec<_>>>()
 }
 
+/// The red tiles have to form a closed loop of horizontal and vertical lines.
+pub fn validate(input: &[Coords]) -> Vec<Diagnostic> {
+    let len = input.len();
+    if len < 4 {
+        return vec![Diagnostic::new("input", format!("{} points can't form a loop", len))];
+    }
+
+    (0..len)
+        .filter_map(|i| {
+            let (a, b) = (&input[i], &input[(i + 1) % len]);
+            let location = format!("points {} and {}", i + 1, (i + 1) % len + 1);
+
+            if a.0 == b.0 && a.1 == b.1 {
+                Some(Diagnostic::new(location, "are the same"))
+            } else if a.0 != b.0 && a.1 != b.1 {
+                Some(Diagnostic::new(
+                    location,
+                    format!("{},{} and {},{} are not on a horizontal or vertical line", a.0, a.1, b.0, b.1),
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
 fn area(a: &Coords, b: &Coords) -> Base {
     (1 + (b.1 - a.1).abs()) * (1 + (b.0 - a.0).abs())
 }
 
-#[aoc(day09, part1)]
-pub fn solve_part1(input: &[Coords]) -> Result<Base> {
+/// Opposite corners of the largest rectangle between two red tiles.
+fn largest_rectangle(input: &[Coords]) -> Option<(&Coords, &Coords)> {
     let len = input.len();
-    let mut max = 0;
+    let mut max = None;
 
     for i in 0..len {
         for j in i+1..len {
-            max = std::cmp::max(max, area(&input[i], &input[j]));
+            if max.is_none_or(|(a, b)| area(a, b) < area(&input[i], &input[j])) {
+                max = Some((&input[i], &input[j]));
+            }
         }
     }
 
-    Ok(max)
+    max
+}
+
+#[aoc(day09, part1)]
+pub fn solve_part1(input: &[Coords]) -> Result<Base> {
+    Ok(largest_rectangle(input).map(|(a, b)| area(a, b)).unwrap_or(0))
+}
+
+/// The loop of red tiles and the rectangle of part 1.
+pub fn render(input: &[Coords]) -> Result<Vec<Frame>> {
+    let width = input.iter().map(|c| c.0 + 1).max().unwrap_or(0);
+    let height = input.iter().map(|c| c.1 + 1).max().unwrap_or(0);
+    let mut frame = Frame::new(format!("{} red tiles", input.len()), width, height);
+
+    frame.polygon(input.iter().map(|c| (c.0, c.1)).collect(), Color::Primary);
+    if let Some((a, b)) = largest_rectangle(input) {
+        frame.title = format!("{}, largest rectangle {}", frame.title, area(a, b));
+        frame.rect((a.0, a.1), (b.0, b.1), Color::Highlight);
+    }
+
+    Ok(vec![frame])
 }
 
 #[aoc(day09, part2)]
@@ -84,4 +125,40 @@ pub fn solve_part2(input: &[Coords]) -> Result<i32> {
 #[cfg(test)]
 mod test {
     use super::*;
+
+    fn input() -> Result<Vec<Coords>> {
+        let input = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+";
+        input_generator(input)
+    }
+
+    #[test]
+    fn part1() -> Result<()> {
+        let input = input()?;
+        Ok(assert_eq!(solve_part1(&input)?, 50))
+    }
+
+    #[test]
+    fn validation() -> Result<()> {
+        assert!(validate(&input()?).is_empty());
+
+        let input = input_generator("1,1\n5,1\n5,5\n2,4\n")?;
+        let messages = validate(&input).into_iter().map(|d| d.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "points 3 and 4: 5,5 and 2,4 are not on a horizontal or vertical line",
+                "points 4 and 1: 2,4 and 1,1 are not on a horizontal or vertical line",
+            ]
+        );
+
+        Ok(())
+    }
 }
\ No newline at end of file
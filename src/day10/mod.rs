@@ -1,17 +1,17 @@
-use std::str::FromStr;
+use std::{io::BufRead, str::FromStr};
 
 use anyhow::{Context, Error, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 use microlp::{OptimizationDirection, Problem};
 
-use crate::utils::AocError;
+use crate::utils::{AocError, Cancel, Diagnostic, stream_lines};
 
 #[derive(Debug, Clone)]
 pub struct Machine {
-    lights: Vec<char>,
-    buttons: Vec<Vec<usize>>,
-    joltage: Vec<usize>,
+    pub(crate) lights: Vec<char>,
+    pub(crate) buttons: Vec<Vec<usize>>,
+    pub(crate) joltage: Vec<usize>,
 }
 
 impl FromStr for Machine {
@@ -72,76 +72,139 @@ pub fn input_generator(input: &str) -> Result<Vec<Machine>> {
         .collect::<Result<Vec<_>>>()
 }
 
-#[aoc(day10, part1)]
-pub fn solve_part1(input: &[Machine]) -> Result<usize> {
-    let minima = input.iter().map(|m| {
-        let buttons = m.buttons.clone();
-        for chunk_size in 1..buttons.len() {
-            for combo in buttons.iter().combinations(chunk_size).collect::<Vec<_>>() {
-                let mut counts = vec![0; m.lights.len()];
-                for toggle in combo {
-                    for b in toggle {
-                        counts[*b] += 1;
-                    }
-                }
+/// Machines, buttons and lights are all numbered from 1 in the messages.
+pub fn validate(input: &[Machine]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    for (i, m) in input.iter().enumerate() {
+        let location = format!("machine {}", i + 1);
+        let lights = m.lights.len();
+
+        if let Some(c) = m.lights.iter().find(|c| **c != '.' && **c != '#') {
+            diagnostics.push(Diagnostic::new(&location, format!("unexpected light state '{}'", c)));
+        }
+
+        for (b, button) in m.buttons.iter().enumerate() {
+            for light in button.iter().filter(|l| **l >= lights) {
+                diagnostics.push(Diagnostic::new(
+                    &location,
+                    format!("button {} toggles light {} but there are only {} lights", b + 1, light + 1, lights),
+                ));
+            }
+        }
+
+        if m.joltage.len() != lights {
+            diagnostics.push(Diagnostic::new(
+                &location,
+                format!("{} joltage requirements for {} lights", m.joltage.len(), lights),
+            ));
+        }
+    }
+
+    diagnostics
+}
 
-                if m.lights.iter().enumerate().fold(true, |acc, (pos, c)| acc & match (pos, c) {
-                        (pos, '.') => counts[pos] % 2 == 0,
-                        (pos, '#') => counts[pos] % 2 == 1,
-                        _ => unreachable!(),
-                }) {
-                    return chunk_size;
+/// Reads machines one line at a time and folds `f` over them.
+fn fold_stream<T>(
+    reader: impl BufRead,
+    init: T,
+    f: impl Fn(T, &Machine) -> Result<T>,
+) -> Result<T> {
+    stream_lines(reader).try_fold(init, |acc, l| f(acc, &Machine::from_str(&l?)?))
+}
+
+fn fewest_presses_lights(m: &Machine, cancel: &Cancel) -> Result<usize> {
+    let buttons = m.buttons.clone();
+    for chunk_size in 1..buttons.len() {
+        for combo in buttons.iter().combinations(chunk_size) {
+            cancel.check()?;
+            let mut counts = vec![0; m.lights.len()];
+            for toggle in combo {
+                for b in toggle {
+                    counts[*b] += 1;
                 }
             }
+
+            if m.lights.iter().enumerate().fold(true, |acc, (pos, c)| acc & match (pos, c) {
+                    (pos, '.') => counts[pos] % 2 == 0,
+                    (pos, '#') => counts[pos] % 2 == 1,
+                    _ => unreachable!(),
+            }) {
+                return Ok(chunk_size);
+            }
         }
+    }
 
-        0
-    }).collect::<Vec<_>>();
+    Ok(0)
+}
+
+#[aoc(day10, part1)]
+pub fn solve_part1(input: &[Machine]) -> Result<usize> {
+    solve_part1_with(input, &Cancel::default())
+}
+
+pub fn solve_part1_with(input: &[Machine], cancel: &Cancel) -> Result<usize> {
+    let minima = input
+        .iter()
+        .map(|m| fewest_presses_lights(m, cancel))
+        .collect::<Result<Vec<_>>>()?;
 
     Ok(minima.into_iter().sum())
 }
 
+pub fn solve_part1_stream(reader: impl BufRead, cancel: &Cancel) -> Result<usize> {
+    fold_stream(reader, 0, |sum, m| Ok(sum + fewest_presses_lights(m, cancel)?))
+}
+
+fn fewest_presses_joltage(machine: &Machine) -> Result<f64> {
+    let buttons = machine.buttons.clone();
+    let joltage = machine.joltage.clone();
+
+    let mut problem = Problem::new(OptimizationDirection::Minimize);
+
+    let variables = buttons
+        .iter()
+        .map(|_| problem.add_integer_var(1.0, (0, i32::MAX)))
+        .collect::<Vec<_>>();
+
+    joltage.iter().enumerate().for_each(|(jpos, j)| {
+        problem.add_constraint(
+            variables
+                .iter()
+                .enumerate()
+                .map(|(bpos, &v)| {
+                    let factor = if buttons[bpos].contains(&jpos) {
+                        1.0f64
+                    } else {
+                        0.0f64
+                    };
+
+                    (v, factor)
+                })
+                .collect::<Vec<_>>(),
+            microlp::ComparisonOp::Eq,
+            *j as f64,
+        )
+    });
+
+    let solution = problem.solve().context("Could not solve ILP")?;
+    Ok(solution.objective())
+}
+
 #[aoc(day10, part2)]
 pub fn solve_part2(input: &[Machine]) -> Result<f64> {
     let mut minima = vec![];
     for machine in input {
-        let buttons = machine.buttons.clone();
-        let joltage = machine.joltage.clone();
-
-        let mut problem = Problem::new(OptimizationDirection::Minimize);
-
-        let variables = buttons
-            .iter()
-            .map(|_| problem.add_integer_var(1.0, (0, i32::MAX)))
-            .collect::<Vec<_>>();
-
-        joltage.iter().enumerate().for_each(|(jpos, j)| {
-            problem.add_constraint(
-                variables
-                    .iter()
-                    .enumerate()
-                    .map(|(bpos, &v)| {
-                        let factor = if buttons[bpos].contains(&jpos) {
-                            1.0f64
-                        } else {
-                            0.0f64
-                        };
-
-                        (v, factor)
-                    })
-                    .collect::<Vec<_>>(),
-                microlp::ComparisonOp::Eq,
-                *j as f64,
-            )
-        });
-
-        let solution = problem.solve().context("Could not solve ILP")?;
-        minima.push(solution.objective());
+        minima.push(fewest_presses_joltage(machine)?);
     }
 
     Ok(minima.into_iter().sum())
 }
 
+pub fn solve_part2_stream(reader: impl BufRead) -> Result<f64> {
+    fold_stream(reader, 0.0, |sum, m| Ok(sum + fewest_presses_joltage(m)?))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -165,4 +228,45 @@ mod test {
         let input = input()?;
         Ok(assert_eq!(solve_part2(&input)?, 33.0))
     }
+
+    #[test]
+    fn stream() -> Result<()> {
+        let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+        assert_eq!(solve_part1_stream(input.as_bytes(), &Cancel::default())?, 7);
+        assert_eq!(solve_part2_stream(input.as_bytes())?, 33.0);
+        Ok(())
+    }
+
+    #[test]
+    fn validation() -> Result<()> {
+        assert!(validate(&input()?).is_empty());
+
+        let input = input_generator("[.#] (0) (1,2) {3,5}\n[.x] (0) {1}")?;
+        let messages = validate(&input).into_iter().map(|d| d.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "machine 1: button 2 toggles light 3 but there are only 2 lights",
+                "machine 2: unexpected light state 'x'",
+                "machine 2: 1 joltage requirements for 2 lights",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel() -> Result<()> {
+        // light 0 can't be switched on, so every combination would be tried
+        let input = input_generator(&format!("[#.] {} {{1,1}}", "(1) ".repeat(40)))?;
+        let cancel = Cancel::after(std::time::Duration::from_millis(10));
+
+        let err = solve_part1_with(&input, &cancel).unwrap_err();
+        assert!(matches!(err.downcast_ref::<AocError>(), Some(AocError::Cancelled)));
+
+        Ok(())
+    }
 }
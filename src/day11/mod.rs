@@ -2,9 +2,8 @@ use std::collections::HashMap;
 
 use anyhow::{Context, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
-use memoize::memoize;
 
-use crate::utils::AocError;
+use crate::utils::{AocError, BigUint, Cancel};
 
 type Graph = HashMap<String, Vec<String>>;
 
@@ -29,6 +28,46 @@ fn parse_line(s: &str) -> Result<(String, Vec<String>)> {
     Ok((left, right))
 }
 
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Start of the paths in part 1.
+    pub you: String,
+    /// End of the paths in both parts.
+    pub out: String,
+    /// Start of the paths in part 2.
+    pub svr: String,
+    /// Devices every path in part 2 has to visit.
+    pub fft: String,
+    pub dac: String,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            you: "you".to_string(),
+            out: "out".to_string(),
+            svr: "svr".to_string(),
+            fft: "fft".to_string(),
+            dac: "dac".to_string(),
+        }
+    }
+}
+
+impl crate::utils::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let value = value.to_string();
+        match key {
+            "you" => self.you = value,
+            "out" => self.out = value,
+            "svr" => self.svr = value,
+            "fft" => self.fft = value,
+            "dac" => self.dac = value,
+            _ => Err(AocError::UnknownParameter(key.to_string()))?,
+        }
+        Ok(())
+    }
+}
+
 #[aoc_generator(day11)]
 pub fn input_generator(input: &str) -> Result<Graph> {
     input
@@ -38,12 +77,13 @@ pub fn input_generator(input: &str) -> Result<Graph> {
         .collect::<Result<Graph>>()
 }
 
-fn find_all(graph: &Graph, start: String, end: String) -> Option<Vec<Vec<String>>> {
+fn find_all(graph: &Graph, start: String, end: String, cancel: &Cancel) -> Result<Vec<Vec<String>>> {
     let mut todo = vec![vec![start]];
     let mut results = vec![];
 
     while let Some(node) = todo.pop() {
-        let last = node.last()?;
+        cancel.check()?;
+        let last = node.last().ok_or(AocError::GenericError)?;
         let next = graph.get(last);
         if next.is_none() {
             continue;
@@ -52,7 +92,7 @@ fn find_all(graph: &Graph, start: String, end: String) -> Option<Vec<Vec<String>
 
         for n in next {
             let mut path = node.clone();
-            if path.contains(&n) {
+            if path.contains(n) {
                 continue;
             }
 
@@ -66,44 +106,56 @@ fn find_all(graph: &Graph, start: String, end: String) -> Option<Vec<Vec<String>
         }
     }
 
-    Some(results)
+    Ok(results)
 }
 
 #[aoc(day11, part1)]
 pub fn solve_part1(input: &Graph) -> Result<usize> {
-    Ok(find_all(input, "you".to_string(), "out".to_string())
-        .ok_or(AocError::GenericError)
+    solve_part1_with(input, &Params::default(), &Cancel::default())
+}
+
+pub fn solve_part1_with(input: &Graph, params: &Params, cancel: &Cancel) -> Result<usize> {
+    Ok(find_all(input, params.you.clone(), params.out.clone(), cancel)
         .context("Could not find all paths")?
         .len())
 }
 
-#[memoize(Ignore: graph)]
-fn count_all(graph: &Graph, start: String, end: String) -> usize {
+fn count_all(graph: &Graph, start: &str, end: &str) -> BigUint {
+    count_paths(graph, start, end, &mut HashMap::new())
+}
+
+/// Counts the paths from `start` to `end`, remembering the counts of every
+/// visited node in `cache`. Only valid for a single `end`.
+fn count_paths<'a>(graph: &'a Graph, start: &'a str, end: &str, cache: &mut HashMap<&'a str, BigUint>) -> BigUint {
     if start == end {
-        return 1;
+        return BigUint::from(1u32);
     }
-
-    let neighbors = graph.get(&start);
-    if neighbors.is_none() {
-        return 0;
+    if let Some(paths) = cache.get(start) {
+        return paths.clone();
     }
 
-    let neighbors = neighbors.unwrap();
-    let mut paths = 0;
-    for n in neighbors {
-        paths += count_all(graph, n.clone(), end.clone());
+    let mut paths = BigUint::default();
+    for n in graph.get(start).into_iter().flatten() {
+        paths += count_paths(graph, n, end, cache);
     }
+    cache.insert(start, paths.clone());
     paths
 }
 
 #[aoc(day11, part2)]
-pub fn solve_part2(input: &Graph) -> Result<usize> {
-    let fft_out = count_all(input, "fft".to_string(), "out".to_string());
-    let dac_out = count_all(input, "dac".to_string(), "out".to_string());
-    let fft_dac = count_all(input, "fft".to_string(), "dac".to_string());
-    let dac_fft = count_all(input, "dac".to_string(), "fft".to_string());
-    let svr_dac = count_all(input, "svr".to_string(), "dac".to_string());
-    let svr_fft = count_all(input, "svr".to_string(), "fft".to_string());
+pub fn solve_part2(input: &Graph) -> Result<BigUint> {
+    solve_part2_with(input, &Params::default())
+}
+
+pub fn solve_part2_with(input: &Graph, params: &Params) -> Result<BigUint> {
+    let Params { out, svr, fft, dac, .. } = params;
+
+    let fft_out = count_all(input, fft, out);
+    let dac_out = count_all(input, dac, out);
+    let fft_dac = count_all(input, fft, dac);
+    let dac_fft = count_all(input, dac, fft);
+    let svr_dac = count_all(input, svr, dac);
+    let svr_fft = count_all(input, svr, fft);
 
     Ok(svr_dac * dac_fft * fft_out + svr_fft * fft_dac * dac_out)
 }
@@ -111,6 +163,7 @@ pub fn solve_part2(input: &Graph) -> Result<usize> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn part1() -> Result<()> {
@@ -146,6 +199,36 @@ ggg: out
 hhh: out
 ";
         let input = input_generator(input)?;
-        Ok(assert_eq!(solve_part2(&input)?, 2))
+        Ok(assert_eq!(solve_part2(&input)?, BigUint::from(2u32)))
+    }
+
+    #[test]
+    fn count_beyond_u128() -> Result<()> {
+        // 130 diamonds in a row double the number of paths each time
+        let input = (0..130)
+            .map(|i| format!("n{i}: l{i} r{i}\nl{i}: n{}\nr{i}: n{}\n", i + 1, i + 1))
+            .collect::<String>();
+        let input = input_generator(&input)?;
+
+        let count = count_all(&input, "n0", "n130");
+        Ok(assert_eq!(count, BigUint::from(2u32).pow(130)))
+    }
+
+    /// Edges only go from lower to higher nodes, so the graph has no cycles.
+    fn dag() -> impl Strategy<Value = (usize, Vec<bool>)> {
+        (2usize..8).prop_flat_map(|n| (Just(n), prop::collection::vec(any::<bool>(), n * n)))
+    }
+
+    proptest! {
+        #[test]
+        fn counting_matches_enumerating_on_dags((n, edges) in dag()) {
+            let name = |i: usize| format!("n{}", i);
+            let graph = (0..n)
+                .map(|i| (name(i), (i + 1..n).filter(|j| edges[i * n + j]).map(name).collect()))
+                .collect::<Graph>();
+
+            let paths = find_all(&graph, name(0), name(n - 1), &Cancel::default()).unwrap();
+            prop_assert_eq!(count_all(&graph, &name(0), &name(n - 1)), BigUint::from(paths.len()));
+        }
     }
 }
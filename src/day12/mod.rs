@@ -3,7 +3,10 @@ use std::str::FromStr;
 use anyhow::{Context, Error, Result};
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::utils::AocError;
+use crate::{
+    utils::{AocError, Diagnostic},
+    viz::{Color, Frame},
+};
 
 type Shape = Vec<Vec<char>>;
 
@@ -17,9 +20,9 @@ fn parse_shape(s: &str) -> Result<Shape> {
 
 #[derive(Debug, Clone)]
 pub struct Tree {
-    width: usize,
-    height: usize,
-    presents: Vec<usize>,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) presents: Vec<usize>,
 }
 
 impl FromStr for Tree {
@@ -89,6 +92,22 @@ pub fn input_generator(input: &str) -> Result<(Vec<Shape>, Vec<Tree>)> {
     Ok((shapes, trees))
 }
 
+pub fn validate(input: &(Vec<Shape>, Vec<Tree>)) -> Vec<Diagnostic> {
+    let (shapes, trees) = input;
+
+    trees
+        .iter()
+        .enumerate()
+        .filter(|(_, tree)| tree.presents.len() > shapes.len())
+        .map(|(i, tree)| {
+            Diagnostic::new(
+                format!("tree {}", i + 1),
+                format!("names {} shapes but only {} were given", tree.presents.len(), shapes.len()),
+            )
+        })
+        .collect()
+}
+
 fn count_spaces(s: &Shape) -> usize {
     s.iter().map(|l| l.iter().filter(|&&c| c == '#').count()).sum()
 }
@@ -107,6 +126,36 @@ pub fn solve_part1(input: &(Vec<Shape>, Vec<Tree>)) -> Result<usize> {
     }).count())
 }
 
+/// One frame per tree, its area filled row by row with the spaces each kind
+/// of present needs. That's all part 1 looks at, presents aren't actually packed.
+pub fn render(input: &(Vec<Shape>, Vec<Tree>)) -> Result<Vec<Frame>> {
+    let (shapes, trees) = input;
+    let spaces = shapes.iter().map(count_spaces).collect::<Vec<_>>();
+
+    Ok(trees
+        .iter()
+        .enumerate()
+        .map(|(i, tree)| {
+            let available = tree.width * tree.height;
+            let mut cells = tree
+                .presents
+                .iter()
+                .enumerate()
+                .flat_map(|(p, c)| std::iter::repeat_n(p, c * spaces.get(p).unwrap_or(&0)));
+            let required = cells.clone().count();
+
+            let fits = if required <= available { "fits" } else { "too small" };
+            let title = format!("tree {}: {}x{}, {} of {} spaces, {}", i + 1, tree.width, tree.height, required, available, fits);
+            let mut frame = Frame::new(title, tree.width as i64, tree.height as i64);
+            for (pos, p) in (0..available).zip(&mut cells) {
+                frame.cell((pos % tree.width) as i64, (pos / tree.width) as i64, '#', Color::Accent(p));
+            }
+
+            frame
+        })
+        .collect())
+}
+
 #[aoc(day12, part2)]
 pub fn solve_part2(_input: &(Vec<Shape>, Vec<Tree>)) -> Result<i32> {
     Ok(0)
@@ -162,4 +211,27 @@ mod test {
         // that unfortunately is not sufficient for the example.
         Ok(assert_eq!(solve_part1(&input)?, 3))
     }
+
+    #[test]
+    fn rendering() -> Result<()> {
+        let frames = render(&input()?)?;
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].title, "tree 1: 4x4, 14 of 16 spaces, fits");
+        assert_eq!(frames[0].items.len(), 14);
+        assert_eq!(frames[2].title, "tree 3: 12x5, 49 of 60 spaces, fits");
+
+        Ok(())
+    }
+
+    #[test]
+    fn validation() -> Result<()> {
+        assert!(validate(&input()?).is_empty());
+
+        let input = input_generator("0:\n##\n\n2x2: 1\n3x3: 1 1\n")?;
+        let messages = validate(&input).into_iter().map(|d| d.to_string()).collect::<Vec<_>>();
+        assert_eq!(messages, vec!["tree 2: names 2 shapes but only 1 were given"]);
+
+        Ok(())
+    }
 }
@@ -3,6 +3,10 @@
 use aoc_runner_derive::aoc_lib;
 extern crate rayon;
 
+#[cfg(feature = "alloc-stats")]
+pub mod alloc;
+#[cfg(feature = "capi")]
+pub mod capi;
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -15,6 +19,18 @@ pub mod day09;
 pub mod day10;
 pub mod day11;
 pub mod day12;
+#[cfg(feature = "python")]
+mod python;
+pub mod runner;
+#[cfg(test)]
+mod snapshot;
+#[cfg(feature = "serve")]
+pub mod serve;
 pub mod utils;
+pub mod viz;
+pub mod y2025;
+pub mod y2026;
+
+pub use runner::{Answer, solve_str};
 
 aoc_lib! { year = 2025 }
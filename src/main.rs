@@ -0,0 +1,284 @@
+use std::{
+    io::{BufRead, IsTerminal},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use aoc_2025::{
+    runner::{self, Report, Season, Settings},
+    utils::{Cancel, Progress, parse_override},
+    viz::Backend,
+};
+use anyhow::Context;
+use clap::Parser;
+
+/// Runs the solutions against their puzzle inputs and reports timings.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// Run the solutions of this event
+    #[arg(short, long, default_value_t = runner::DEFAULT_YEAR)]
+    year: u32,
+
+    /// Only run this day
+    #[arg(short, long)]
+    day: Option<u32>,
+
+    /// Only run this part
+    #[arg(short, long)]
+    part: Option<u32>,
+
+    /// Read the input from this file instead of input/<year>/day<day>.txt, `-` reads stdin
+    #[arg(short, long, requires = "day")]
+    input: Option<String>,
+
+    /// Override a puzzle parameter of the day, e.g. `-P connections=10`
+    #[arg(short = 'P', long = "param", value_name = "KEY=VALUE", value_parser = parse_override, requires = "day")]
+    params: Vec<(String, String)>,
+
+    /// Cancel a part after this many seconds and continue with the next one
+    #[arg(short, long)]
+    timeout: Option<f64>,
+
+    /// Process the input incrementally instead of reading it into memory first
+    #[arg(short, long, requires_all = ["day", "part"])]
+    stream: bool,
+
+    /// Check the inputs for problems without solving them
+    #[arg(long, conflicts_with_all = ["part", "stream", "params", "timeout"])]
+    validate: bool,
+
+    /// Draw the state of the day instead of solving it, as `ansi`, `text` or `svg`
+    #[arg(short, long, value_name = "BACKEND", requires = "day", conflicts_with_all = ["part", "stream", "params", "timeout", "validate"])]
+    render: Option<Backend>,
+
+    /// Write the rendering to this file instead of stdout
+    #[arg(short, long, requires = "render")]
+    output: Option<String>,
+}
+
+/// Draws progress as a single line on stderr, at most ten times a second,
+/// and clears it again when dropped.
+struct ProgressLine {
+    label: String,
+    last: Mutex<Option<Instant>>,
+}
+
+impl ProgressLine {
+    fn for_terminal(day: u32, part: u32) -> Option<Arc<dyn Progress>> {
+        if !std::io::stderr().is_terminal() {
+            return None;
+        }
+
+        Some(Arc::new(ProgressLine {
+            label: format!("Day {} - Part {}", day, part),
+            last: Mutex::new(None),
+        }))
+    }
+}
+
+impl Progress for ProgressLine {
+    fn report(&self, done: u64, total: u64) {
+        let mut last = self.last.lock().unwrap();
+        if last.is_some_and(|l| l.elapsed() < Duration::from_millis(100)) {
+            return;
+        }
+        *last = Some(Instant::now());
+
+        let percent = (done * 100).checked_div(total).unwrap_or(0);
+        eprint!("\r\x1b[2K{}: {}/{} ({}%)", self.label, done, total, percent);
+    }
+}
+
+impl Drop for ProgressLine {
+    fn drop(&mut self) {
+        if self.last.get_mut().is_ok_and(|l| l.is_some()) {
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+fn print(day: u32, part: u32, result: Result<Report, runner::Failure>) {
+    match result {
+        Err(e) if e.is_cancelled() => eprintln!("Day {} - Part {}: CANCELLED\n", day, part),
+        Ok(report) => {
+            println!("Day {} - Part {}: {}", day, part, report.answer);
+            for note in &report.notes {
+                println!("\t{}", note);
+            }
+            if let Some(generator) = report.generator {
+                println!("\tgenerator: {},", generator);
+            }
+            println!("\trunner: {}\n", report.runner);
+        }
+        Err(e) => eprintln!("Day {} - Part {}: {}\n", day, part, e),
+    }
+}
+
+fn stream(season: &Season, args: &Args, settings: impl Fn(u32, u32) -> Settings) -> anyhow::Result<()> {
+    let (day, part) = (args.day.unwrap_or_default(), args.part.unwrap_or_default());
+    let stream = runner::find_stream(season.year, day, part)
+        .ok_or(anyhow::anyhow!("Day {} - Part {} can't be streamed", day, part))?;
+    let settings = settings(day, part);
+    runner::check_params(&[stream.takes], &settings)?;
+    let mut reader = runner::open_input(season.year, day, args.input.as_deref())?;
+
+    let result = (stream.run)(&mut reader as &mut dyn BufRead, &settings);
+    print(day, part, result);
+    Ok(())
+}
+
+/// Prints the problems found in the inputs and returns whether everything was fine.
+fn validate(season: &Season, args: &Args) -> bool {
+    let mut valid = true;
+
+    if let Some(day) = args.day
+        && runner::find_validator(season.year, day).is_none()
+    {
+        eprintln!("Day {}: no validation available", day);
+        return false;
+    }
+
+    for validator in season.validators {
+        if args.day.is_some_and(|d| d != validator.day) {
+            continue;
+        }
+
+        let input = match runner::read_input(season.year, validator.day, args.input.as_deref()) {
+            Ok(input) => input,
+            Err(e) => {
+                if args.day.is_some() {
+                    eprintln!("Day {}: {:#}", validator.day, e);
+                    valid = false;
+                }
+                continue;
+            }
+        };
+
+        match (validator.run)(&input) {
+            Ok(diagnostics) if diagnostics.is_empty() => println!("Day {}: ok", validator.day),
+            Ok(diagnostics) => {
+                valid = false;
+                println!("Day {}:", validator.day);
+                for d in diagnostics {
+                    println!("\t{}", d);
+                }
+            }
+            Err(e) => {
+                valid = false;
+                println!("Day {}: FAILED while generating:\n{:#?}", validator.day, e);
+            }
+        }
+    }
+
+    valid
+}
+
+fn render(season: &Season, args: &Args, backend: Backend) -> anyhow::Result<()> {
+    let day = args.day.unwrap_or_default();
+    let renderer = runner::find_renderer(season.year, day)
+        .ok_or(anyhow::anyhow!("Day {} can't be rendered", day))?;
+    let input = runner::read_input(season.year, day, args.input.as_deref())?;
+
+    let frames = (renderer.run)(&input)?;
+    let output = backend.render(&frames);
+    match &args.output {
+        Some(path) => std::fs::write(path, output).context(format!("Could not write {}", path)),
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let Some(season) = runner::season(args.year) else {
+        eprintln!("No solutions for {}", args.year);
+        std::process::exit(1);
+    };
+
+    let settings = Settings {
+        params: args.params.clone(),
+        ..Settings::default()
+    };
+    // every part gets its own deadline and progress line
+    let settings = |day, part| Settings {
+        cancel: args
+            .timeout
+            .map(|t| Cancel::after(Duration::from_secs_f64(t)))
+            .unwrap_or_default(),
+        progress: ProgressLine::for_terminal(day, part),
+        ..settings.clone()
+    };
+
+    // renderings go to stdout on their own so they can be piped into a file
+    if let Some(backend) = args.render {
+        if let Err(e) = render(season, &args, backend) {
+            eprintln!("{:#}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    println!("Advent of code {}", season.year);
+
+    if args.validate {
+        if !validate(season, &args) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if args.stream {
+        if let Err(e) = stream(season, &args, settings) {
+            eprintln!("{:#}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let selected = season
+        .solutions
+        .iter()
+        .filter(|s| args.day.is_none_or(|d| d == s.day) && args.part.is_none_or(|p| p == s.part))
+        .map(|s| s.takes)
+        .collect::<Vec<_>>();
+    let params = Settings {
+        params: args.params.clone(),
+        ..Settings::default()
+    };
+    if let Err(e) = runner::check_params(&selected, &params) {
+        eprintln!("{:#}", e);
+        std::process::exit(1);
+    }
+
+    // parts of a day share their input, which matters when it comes from stdin
+    for parts in season.solutions.chunk_by(|a, b| a.day == b.day) {
+        let day = parts[0].day;
+        if args.day.is_some_and(|d| d != day) {
+            continue;
+        }
+
+        let input = runner::read_input(season.year, day, args.input.as_deref());
+
+        for solution in parts {
+            if args.part.is_some_and(|p| p != solution.part) {
+                continue;
+            }
+
+            match &input {
+                Ok(input) => {
+                    let result = (solution.run)(input, &settings(day, solution.part));
+                    print(day, solution.part, result);
+                }
+                Err(e) => {
+                    if args.day.is_some() {
+                        eprintln!("Day {} - Part {}: {:#}\n", day, solution.part, e);
+                    }
+                }
+            }
+        }
+    }
+}
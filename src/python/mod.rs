@@ -0,0 +1,200 @@
+//! Python extension module. Build it as a shared library with
+//! `cargo rustc --lib --release --features python --crate-type cdylib` and
+//! copy `target/release/libaoc_2025.so` to `aoc_2025.so` somewhere on the
+//! Python path.
+//!
+//! ```python
+//! import aoc_2025
+//!
+//! aoc_2025.solve(8, 1, text)
+//! aoc_2025.day08.parse(text)  # [(162, 817, 812), ...]
+//! aoc_2025.day08.part2(text)
+//! ```
+//!
+//! Answers are Python ints, parse errors raise `ValueError` and failing
+//! solvers `RuntimeError`.
+
+use pyo3::{
+    IntoPyObjectExt,
+    exceptions::{PyRuntimeError, PyValueError},
+    prelude::*,
+    types::PyInt,
+};
+
+use crate::runner::{self, Failure, Settings};
+
+fn value_error(e: anyhow::Error) -> PyErr {
+    PyValueError::new_err(format!("{:#}", e))
+}
+
+/// Solves a part of the default year, see [`runner::solve_str`].
+#[pyfunction]
+fn solve<'py>(py: Python<'py>, day: u32, part: u32, input: &str) -> PyResult<Bound<'py, PyAny>> {
+    let solution = runner::find(runner::DEFAULT_YEAR, day, part)
+        .ok_or_else(|| PyValueError::new_err(format!("No solution for day {} part {}", day, part)))?;
+
+    // the solvers don't need Python, other threads may run in the meantime
+    let result = py.detach(|| (solution.run)(input.trim_end_matches('\n'), &Settings::default()));
+    let answer = match result {
+        Ok(report) => report.answer,
+        Err(Failure::Generating(e)) => return Err(value_error(e)),
+        Err(Failure::Running(e)) => return Err(PyRuntimeError::new_err(format!("{:#}", e))),
+    };
+
+    py.get_type::<PyInt>()
+        .call1((&answer,))
+        .or_else(|_| answer.into_bound_py_any(py))
+}
+
+/// Adds `module` as a submodule with `parse`, `part1` and `part2`. `parse`
+/// returns the output of the day's `input_generator`, turned into plain
+/// Python values by `convert`.
+macro_rules! day {
+    ($module:ident, $day:literal, |$generated:ident| $convert:expr) => {
+        mod $module {
+            use super::*;
+
+            #[pyfunction]
+            fn parse<'py>(py: Python<'py>, input: &str) -> PyResult<Bound<'py, PyAny>> {
+                let $generated = crate::$module::input_generator(input).map_err(value_error)?;
+                $convert.into_bound_py_any(py)
+            }
+
+            #[pyfunction]
+            fn part1<'py>(py: Python<'py>, input: &str) -> PyResult<Bound<'py, PyAny>> {
+                solve(py, $day, 1, input)
+            }
+
+            #[pyfunction]
+            fn part2<'py>(py: Python<'py>, input: &str) -> PyResult<Bound<'py, PyAny>> {
+                solve(py, $day, 2, input)
+            }
+
+            pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
+                let m = PyModule::new(parent.py(), stringify!($module))?;
+                m.add_function(wrap_pyfunction!(parse, &m)?)?;
+                m.add_function(wrap_pyfunction!(part1, &m)?)?;
+                m.add_function(wrap_pyfunction!(part2, &m)?)?;
+                parent.add_submodule(&m)
+            }
+        }
+    };
+}
+
+day!(day01, 1, |ops| ops
+    .iter()
+    .map(|op| match op {
+        crate::day01::Op::Left(n) => ("L", *n),
+        crate::day01::Op::Right(n) => ("R", *n),
+    })
+    .collect::<Vec<_>>());
+day!(day02, 2, |ranges| ranges.iter().map(|r| (r.start, r.end)).collect::<Vec<_>>());
+day!(day03, 3, |banks| banks);
+day!(day04, 4, |map| map);
+day!(day05, 5, |input| (
+    input.0.iter().map(|r| (r.start, r.end)).collect::<Vec<_>>(),
+    input.1
+));
+day!(day06, 6, |worksheet| worksheet);
+day!(day07, 7, |map| map);
+day!(day08, 8, |coords| coords.iter().map(|c| (c.0, c.1, c.2)).collect::<Vec<_>>());
+day!(day09, 9, |coords| coords.iter().map(|c| (c.0, c.1)).collect::<Vec<_>>());
+day!(day10, 10, |machines| machines
+    .iter()
+    .map(|m| (m.lights.iter().collect::<String>(), m.buttons.clone(), m.joltage.clone()))
+    .collect::<Vec<_>>());
+day!(day11, 11, |graph| graph);
+day!(day12, 12, |input| (
+    input
+        .0
+        .iter()
+        .map(|shape| shape.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>())
+        .collect::<Vec<_>>(),
+    input
+        .1
+        .iter()
+        .map(|t| (t.width, t.height, t.presents.clone()))
+        .collect::<Vec<_>>()
+));
+
+#[pymodule]
+fn aoc_2025(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+
+    day01::register(m)?;
+    day02::register(m)?;
+    day03::register(m)?;
+    day04::register(m)?;
+    day05::register(m)?;
+    day06::register(m)?;
+    day07::register(m)?;
+    day08::register(m)?;
+    day09::register(m)?;
+    day10::register(m)?;
+    day11::register(m)?;
+    day12::register(m)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::CString;
+
+    use anyhow::Result;
+    use pyo3::{types::PyDict, wrap_pymodule};
+
+    use super::*;
+
+    /// Runs `code` in the local interpreter with the module imported as `aoc`.
+    fn run(code: &str) -> Result<()> {
+        Python::initialize();
+        Python::attach(|py| {
+            let globals = PyDict::new(py);
+            globals.set_item("aoc", wrap_pymodule!(aoc_2025)(py))?;
+            py.run(&CString::new(code)?, Some(&globals), None)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn solves() -> Result<()> {
+        run(r#"
+day01 = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n"
+assert aoc.solve(1, 2, day01) == 6
+assert aoc.day01.part1(day01) == 3
+assert aoc.day01.parse(day01)[:2] == [("L", 68), ("L", 30)]
+
+# answers beyond 64 bits stay exact
+diamonds = "".join(f"n{i}: l{i} r{i}\nl{i}: n{i + 1}\nr{i}: n{i + 1}\n" for i in range(70))
+graph = f"svr: fft\nfft: n0\nn70: dac\ndac: out\n{diamonds}"
+assert aoc.day11.part2(graph) == 2 ** 70
+"#)
+    }
+
+    #[test]
+    fn returns_native_structures() -> Result<()> {
+        run(r#"
+assert aoc.day08.parse("162,817,812\n57,618,57\n") == [(162, 817, 812), (57, 618, 57)]
+assert aoc.day11.parse("you: a b\na: out\n") == {"you": ["a", "b"], "a": ["out"]}
+assert aoc.day05.parse("3-5\n10-14\n\n1\n5\n") == ([(3, 5), (10, 14)], [1, 5])
+assert aoc.day04.parse(".@\n")[(0, 1)] == "@"
+"#)
+    }
+
+    #[test]
+    fn raises() -> Result<()> {
+        run(r#"
+for call, error in [
+    (lambda: aoc.day02.parse("11-x"), ValueError),
+    (lambda: aoc.solve(2, 1, "11-x"), ValueError),
+    (lambda: aoc.solve(13, 1, ""), ValueError),
+]:
+    try:
+        call()
+        assert False, "no exception"
+    except error:
+        pass
+"#)
+    }
+}
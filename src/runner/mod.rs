@@ -0,0 +1,715 @@
+use std::{
+    fmt::Display,
+    io::{BufRead, Read},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    utils::{AocError, Cancel, Diagnostic, NoProgress, Params, Progress},
+    viz::Frame,
+};
+
+/// Time and, with the `alloc-stats` feature, memory spent in one phase of a solution.
+#[derive(Debug, Clone, Copy)]
+pub struct Measurement {
+    pub time: Duration,
+    #[cfg(feature = "alloc-stats")]
+    pub alloc: crate::alloc::Stats,
+}
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.time)?;
+        #[cfg(feature = "alloc-stats")]
+        write!(f, " ({})", self.alloc)?;
+        Ok(())
+    }
+}
+
+/// The answer to one part of a puzzle, as it's entered on the website.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Answer(pub String);
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub answer: String,
+    /// Streaming solvers that parse as they go have no separate generator phase.
+    pub generator: Option<Measurement>,
+    pub runner: Measurement,
+    /// Extra lines from the solver, e.g. statistics, see [`crate::utils::Notes`].
+    pub notes: Vec<String>,
+}
+
+/// Failure of a solution, tagged with the phase it happened in.
+#[derive(Debug)]
+pub enum Failure {
+    Generating(anyhow::Error),
+    Running(anyhow::Error),
+}
+
+impl Failure {
+    /// Whether the solver gave up because its [`Cancel`] token fired.
+    pub fn is_cancelled(&self) -> bool {
+        match self {
+            Failure::Running(e) => matches!(e.downcast_ref::<AocError>(), Some(AocError::Cancelled)),
+            Failure::Generating(_) => false,
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Generating(e) => write!(f, "FAILED while generating:\n{:#?}", e),
+            Failure::Running(e) => write!(f, "FAILED while running:\n{:#?}", e),
+        }
+    }
+}
+
+/// How a solution should be run.
+#[derive(Clone, Default)]
+pub struct Settings {
+    /// `key=value` overrides of the day's `Params`. Solutions that don't take
+    /// parameters ignore them, [`check_params`] makes sure someone does.
+    pub params: Vec<(String, String)>,
+    /// Checked by the long-running solvers.
+    pub cancel: Cancel,
+    /// Receives updates from the long-running solvers.
+    pub progress: Option<Arc<dyn Progress>>,
+}
+
+impl Settings {
+    pub fn params<P: Params>(&self) -> Result<P> {
+        P::with_overrides(&self.params)
+    }
+
+    pub fn progress(&self) -> &dyn Progress {
+        self.progress.as_deref().unwrap_or(&NoProgress)
+    }
+}
+
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    /// Whether the solver uses the override `key=value`, see [`takes`].
+    pub takes: fn(&str, &str) -> bool,
+    pub run: fn(&str, &Settings) -> Result<Report, Failure>,
+}
+
+/// A solution that reads its input incrementally instead of from a string.
+pub struct Stream {
+    pub day: u32,
+    pub part: u32,
+    pub takes: fn(&str, &str) -> bool,
+    pub run: fn(&mut dyn BufRead, &Settings) -> Result<Report, Failure>,
+}
+
+/// Checks an input for semantic problems without solving it.
+pub struct Validator {
+    pub day: u32,
+    /// Fails if the input doesn't even parse.
+    pub run: fn(&str) -> Result<Vec<Diagnostic>>,
+}
+
+/// Draws the state of a day for inspection, see [`crate::viz`].
+pub struct Renderer {
+    pub day: u32,
+    pub run: fn(&str) -> Result<Vec<Frame>>,
+}
+
+pub fn measure<T>(f: impl FnOnce() -> Result<T>) -> Result<(T, Measurement)> {
+    #[cfg(feature = "alloc-stats")]
+    let tracker = crate::alloc::start();
+    let start = Instant::now();
+
+    let value = f()?;
+
+    let measurement = Measurement {
+        time: start.elapsed(),
+        #[cfg(feature = "alloc-stats")]
+        alloc: tracker.stop(),
+    };
+
+    Ok((value, measurement))
+}
+
+/// Whether `P` has a parameter `key`, whether or not `value` suits it.
+pub fn takes<P: Params>(key: &str, value: &str) -> bool {
+    P::default()
+        .set(key, value)
+        .err()
+        .is_none_or(|e| !matches!(e.downcast_ref::<AocError>(), Some(AocError::UnknownParameter(_))))
+}
+
+/// Fails on the first override in `settings` that none of the selected
+/// solutions, given by their `takes`, would use. Solutions ignore overrides
+/// they don't take, so a parameter of part 2 doesn't break part 1.
+pub fn check_params(takes: &[fn(&str, &str) -> bool], settings: &Settings) -> Result<()> {
+    for (key, value) in &settings.params {
+        if !takes.iter().any(|t| t(key, value)) {
+            Err(AocError::UnknownParameter(key.clone())).context("None of the selected solutions takes this parameter")?;
+        }
+    }
+    Ok(())
+}
+
+/// Registers `module::solver` for a day and part, using `module::input_generator`
+/// to parse the input. Solvers taking more than the input name the extra
+/// arguments, e.g. `day01::solve_part1_with(params)` gets the day's `Params`.
+/// Generators can take them too when named explicitly,
+/// `solution!(2, 1, day02::input_generator_with(params) => day02::solve_part1_with(params))`.
+macro_rules! solution {
+    ($day:literal, $part:literal, $module:ident :: $solver:ident) => {
+        $crate::runner::solution!(@entry $day, $part, $module, $solver, input, _settings, _notes,
+            { $crate::runner::solution!(@takes $module) },
+            {},
+            { $module::input_generator(input) })
+    };
+    ($day:literal, $part:literal, $module:ident :: $solver:ident ($($arg:ident),*)) => {
+        $crate::runner::solution!(@entry $day, $part, $module, $solver, input, settings, notes,
+            { $crate::runner::solution!(@takes $module $(, $arg)*) },
+            {},
+            { $module::input_generator(input) }
+            $(, $crate::runner::solution!(@arg $module, settings, notes, $arg))*)
+    };
+    ($day:literal, $part:literal, $module:ident :: $generator:ident ($($garg:ident),*) => $module2:ident :: $solver:ident ($($arg:ident),*)) => {
+        $crate::runner::solution!(@entry $day, $part, $module2, $solver, input, settings, notes,
+            {
+                |key, value| {
+                    ($crate::runner::solution!(@takes $module $(, $garg)*))(key, value)
+                        || ($crate::runner::solution!(@takes $module2 $(, $arg)*))(key, value)
+                }
+            },
+            // bad parameters fail the solution, not the parsing
+            { $($crate::runner::solution!(@check $module, settings, $garg);)* },
+            { $module::$generator(input $(, $crate::runner::solution!(@arg $module, settings, notes, $garg))*) }
+            $(, $crate::runner::solution!(@arg $module2, settings, notes, $arg))*)
+    };
+    (@takes $module:ident) => {
+        |_, _| false
+    };
+    (@takes $module:ident, params $(, $rest:ident)*) => {
+        $crate::runner::takes::<$module::Params>
+    };
+    (@takes $module:ident, $other:ident $(, $rest:ident)*) => {
+        $crate::runner::solution!(@takes $module $(, $rest)*)
+    };
+    (@check $module:ident, $settings:ident, params) => {
+        $settings.params::<$module::Params>().map_err($crate::runner::Failure::Running)?
+    };
+    (@check $module:ident, $settings:ident, $other:ident) => {
+        ()
+    };
+    (@arg $module:ident, $settings:ident, $notes:ident, params) => {
+        &$settings.params::<$module::Params>()?
+    };
+    (@arg $module:ident, $settings:ident, $notes:ident, cancel) => {
+        &$settings.cancel
+    };
+    (@arg $module:ident, $settings:ident, $notes:ident, progress) => {
+        $settings.progress()
+    };
+    (@arg $module:ident, $settings:ident, $notes:ident, notes) => {
+        &$notes
+    };
+    (@entry $day:literal, $part:literal, $module:ident, $solver:ident, $input:ident, $settings:ident, $notes:ident, $takes:block, $check:block, $generate:block $(, $arg:expr)*) => {
+        $crate::runner::Solution {
+            day: $day,
+            part: $part,
+            takes: $takes,
+            run: |$input, $settings| {
+                use $crate::runner::{measure, Failure, Report};
+
+                let $notes = $crate::utils::Notes::default();
+                $check;
+                let (generated, generator) = measure(|| $generate).map_err(Failure::Generating)?;
+                let (answer, runner) = measure(|| {
+                    $module::$solver(::std::borrow::Borrow::borrow(&generated) $(, $arg)*)
+                })
+                .map_err(Failure::Running)?;
+
+                Ok(Report {
+                    answer: answer.to_string(),
+                    generator: Some(generator),
+                    runner,
+                    notes: $notes.into_lines(),
+                })
+            },
+        }
+    };
+}
+
+pub(crate) use solution;
+
+/// Registers a streaming solver, either one consuming the reader directly,
+/// `stream!(1, 1, day01::solve_part1_stream(params))`, or a streaming generator
+/// feeding a regular solver, `stream!(8, 1, day08::input_generator_stream => day08::solve_part1_with(params))`.
+macro_rules! stream {
+    ($day:literal, $part:literal, $module:ident :: $solver:ident) => {
+        $crate::runner::Stream {
+            day: $day,
+            part: $part,
+            takes: $crate::runner::solution!(@takes $module),
+            run: |reader, _settings| {
+                use $crate::runner::{measure, Failure, Report};
+
+                let (answer, runner) =
+                    measure(|| $module::$solver(reader)).map_err(Failure::Running)?;
+
+                Ok(Report {
+                    answer: answer.to_string(),
+                    generator: None,
+                    runner,
+                    notes: vec![],
+                })
+            },
+        }
+    };
+    ($day:literal, $part:literal, $module:ident :: $solver:ident ($($arg:ident),*)) => {
+        $crate::runner::Stream {
+            day: $day,
+            part: $part,
+            takes: $crate::runner::solution!(@takes $module $(, $arg)*),
+            run: |reader, settings| {
+                use $crate::runner::{measure, Failure, Report};
+
+                let notes = $crate::utils::Notes::default();
+                let (answer, runner) = measure(|| {
+                    $module::$solver(reader $(, $crate::runner::solution!(@arg $module, settings, notes, $arg))*)
+                })
+                .map_err(Failure::Running)?;
+
+                Ok(Report {
+                    answer: answer.to_string(),
+                    generator: None,
+                    runner,
+                    notes: notes.into_lines(),
+                })
+            },
+        }
+    };
+    ($day:literal, $part:literal, $module:ident :: $generator:ident => $module2:ident :: $solver:ident $(($($arg:ident),*))?) => {
+        $crate::runner::Stream {
+            day: $day,
+            part: $part,
+            takes: $crate::runner::solution!(@takes $module2 $($(, $arg)*)?),
+            #[allow(unused_variables)]
+            run: |reader, settings| {
+                use $crate::runner::{measure, Failure, Report};
+
+                let notes = $crate::utils::Notes::default();
+                let (generated, generator) =
+                    measure(|| $module::$generator(reader)).map_err(Failure::Generating)?;
+                let (answer, runner) = measure(|| {
+                    $module2::$solver(
+                        ::std::borrow::Borrow::borrow(&generated)
+                        $($(, $crate::runner::solution!(@arg $module2, settings, notes, $arg))*)?
+                    )
+                })
+                .map_err(Failure::Running)?;
+
+                Ok(Report {
+                    answer: answer.to_string(),
+                    generator: Some(generator),
+                    runner,
+                    notes: notes.into_lines(),
+                })
+            },
+        }
+    };
+}
+
+pub(crate) use stream;
+
+/// Registers `module::validate`, run on the output of `module::input_generator`.
+macro_rules! validator {
+    ($day:literal, $module:ident) => {
+        $crate::runner::Validator {
+            day: $day,
+            run: |input| {
+                let generated = $module::input_generator(input)?;
+                Ok($module::validate(::std::borrow::Borrow::borrow(&generated)))
+            },
+        }
+    };
+}
+
+pub(crate) use validator;
+
+/// Registers `module::render`, run on the output of `module::input_generator`.
+macro_rules! renderer {
+    ($day:literal, $module:ident) => {
+        $crate::runner::Renderer {
+            day: $day,
+            run: |input| {
+                let generated = $module::input_generator(input)?;
+                $module::render(::std::borrow::Borrow::borrow(&generated))
+            },
+        }
+    };
+}
+
+pub(crate) use renderer;
+
+/// All solutions of one Advent of Code event.
+pub struct Season {
+    pub year: u32,
+    pub solutions: &'static [Solution],
+    pub streams: &'static [Stream],
+    pub validators: &'static [Validator],
+    pub renderers: &'static [Renderer],
+}
+
+pub static SEASONS: &[Season] = &[
+    Season {
+        year: 2025,
+        solutions: crate::y2025::SOLUTIONS,
+        streams: crate::y2025::STREAMS,
+        validators: crate::y2025::VALIDATORS,
+        renderers: crate::y2025::RENDERERS,
+    },
+    Season {
+        year: 2026,
+        solutions: crate::y2026::SOLUTIONS,
+        streams: crate::y2026::STREAMS,
+        validators: crate::y2026::VALIDATORS,
+        renderers: crate::y2026::RENDERERS,
+    },
+];
+
+pub static DEFAULT_YEAR: u32 = 2025;
+
+pub fn season(year: u32) -> Option<&'static Season> {
+    SEASONS.iter().find(|s| s.year == year)
+}
+
+pub fn find(year: u32, day: u32, part: u32) -> Option<&'static Solution> {
+    season(year)?
+        .solutions
+        .iter()
+        .find(|s| s.day == day && s.part == part)
+}
+
+pub fn find_stream(year: u32, day: u32, part: u32) -> Option<&'static Stream> {
+    season(year)?
+        .streams
+        .iter()
+        .find(|s| s.day == day && s.part == part)
+}
+
+pub fn find_validator(year: u32, day: u32) -> Option<&'static Validator> {
+    season(year)?.validators.iter().find(|v| v.day == day)
+}
+
+pub fn find_renderer(year: u32, day: u32) -> Option<&'static Renderer> {
+    season(year)?.renderers.iter().find(|r| r.day == day)
+}
+
+/// Solves a part of the [`DEFAULT_YEAR`] with default settings. Doesn't keep
+/// any state between calls and never prints, so it's safe to call from many
+/// threads at once.
+pub fn solve_str(day: u32, part: u32, input: &str) -> Result<Answer> {
+    let solution = find(DEFAULT_YEAR, day, part)
+        .ok_or(AocError::GenericError)
+        .context(format!("No solution for day {} part {}", day, part))?;
+
+    match (solution.run)(input.trim_end_matches('\n'), &Settings::default()) {
+        Ok(report) => Ok(Answer(report.answer)),
+        Err(Failure::Generating(e)) => Err(e.context("Could not parse input")),
+        Err(Failure::Running(e)) => Err(e.context("Could not solve")),
+    }
+}
+
+fn input_path(year: u32, day: u32, path: Option<&str>) -> String {
+    path.map(|p| p.to_string())
+        .unwrap_or_else(|| format!("input/{}/day{}.txt", year, day))
+}
+
+/// Reads the puzzle input the same way cargo-aoc does, from `input/<year>/day<day>.txt`
+/// unless a path is given, and strips trailing newlines. A path of `-` reads stdin.
+pub fn read_input(year: u32, day: u32, path: Option<&str>) -> Result<String> {
+    let mut input = String::new();
+    open_input(year, day, path)?
+        .read_to_string(&mut input)
+        .context("Could not read input")?;
+
+    Ok(input.trim_end_matches('\n').to_string())
+}
+
+/// Opens the puzzle input for streaming, see [`read_input`].
+pub fn open_input(year: u32, day: u32, path: Option<&str>) -> Result<Box<dyn BufRead>> {
+    let path = input_path(year, day, path);
+    if path == "-" {
+        return Ok(Box::new(std::io::stdin().lock()));
+    }
+
+    let file = std::fs::File::open(&path).context(format!("Could not read {}", path))?;
+    Ok(Box::new(std::io::BufReader::new(file)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DAY01: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    #[test]
+    fn runs_registered_solution() -> Result<()> {
+        let solution = find(2025, 1, 2).context("day 1 part 2 not registered")?;
+        let report = (solution.run)(DAY01, &Settings::default())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        Ok(assert_eq!(report.answer, "6"))
+    }
+
+    #[test]
+    fn passes_params() -> Result<()> {
+        let solution = find(2025, 1, 2).context("day 1 part 2 not registered")?;
+        let settings = Settings {
+            params: vec![("start".to_string(), "0".to_string())],
+            ..Settings::default()
+        };
+        let report = (solution.run)(DAY01, &settings).map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        Ok(assert_eq!(report.answer, "4"))
+    }
+
+    #[test]
+    fn solves_amounts_beyond_i32() -> Result<()> {
+        // 50 clicks to 0, then every 100th of the remaining ones, back on 50 before L1
+        let input = "R3000000000\nL1";
+        assert_eq!(solve_str(1, 1, input)?, Answer("0".to_string()));
+        assert_eq!(solve_str(1, 2, input)?, Answer("30000000".to_string()));
+
+        let stream = find_stream(2025, 1, 2).context("day 1 part 2 has no stream")?;
+        let report = (stream.run)(&mut input.as_bytes(), &Settings::default()).map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert_eq!(report.answer, "30000000");
+
+        Ok(())
+    }
+
+    #[test]
+    fn runs_the_lock() -> Result<()> {
+        let settings = Settings {
+            params: vec![("dials".to_string(), "3".to_string())],
+            ..Settings::default()
+        };
+
+        let solution = find(2025, 1, 3).context("day 1 lock not registered")?;
+        let report = (solution.run)("R60\n2L251\n3R52", &settings).map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert_eq!(report.answer, "10-0-0");
+        assert_eq!(
+            report.notes,
+            vec!["dial 1: 0 landed, 1 passed", "dial 2: 1 landed, 3 passed", "dial 3: 1 landed, 1 passed"]
+        );
+
+        // a single dial doesn't know about the others
+        let solution = find(2025, 1, 1).context("day 1 part 1 not registered")?;
+        assert!(matches!((solution.run)(DAY01, &settings), Err(Failure::Running(_))));
+        assert!(check_params(&[solution.takes], &settings).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_params() {
+        let solution = find(2025, 1, 1).unwrap();
+        let settings = Settings {
+            params: vec![("size".to_string(), "10".to_string())],
+            ..Settings::default()
+        };
+
+        assert!(matches!((solution.run)(DAY01, &settings), Err(Failure::Running(_))));
+    }
+
+    #[test]
+    fn rejects_params_for_days_without() {
+        let solution = find(2025, 2, 1).unwrap();
+        let settings = Settings {
+            params: vec![("start".to_string(), "0".to_string())],
+            ..Settings::default()
+        };
+
+        assert!(matches!((solution.run)("11-22", &settings), Err(Failure::Running(_))));
+    }
+
+    #[test]
+    fn checks_params_of_the_selection() -> Result<()> {
+        let settings = |key: &str, value: &str| Settings {
+            params: vec![(key.to_string(), value.to_string())],
+            ..Settings::default()
+        };
+        let takes = |day, part| find(2025, day, part).map(|s| s.takes).context("not registered");
+
+        // day 4 takes no parameters at all
+        let err = check_params(&[takes(4, 1)?], &settings("typo", "1")).unwrap_err();
+        assert!(matches!(err.downcast_ref::<AocError>(), Some(AocError::UnknownParameter(k)) if k == "typo"));
+
+        // part 2 of day 3 uses digits, part 1 ignores it
+        check_params(&[takes(3, 1)?, takes(3, 2)?], &settings("digits", "5"))?;
+        assert!(check_params(&[takes(3, 1)?], &settings("digits", "5")).is_err());
+        // a bad value still belongs to the parameter, solving reports it
+        check_params(&[takes(3, 2)?], &settings("digits", "x"))?;
+
+        let stream = find_stream(2025, 8, 1).context("day 8 part 1 has no stream")?;
+        check_params(&[stream.takes], &settings("connections", "1"))?;
+        assert!(check_params(&[stream.takes], &settings("digits", "1")).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn collects_notes() -> Result<()> {
+        let solution = find(2025, 2, 2).context("day 2 part 2 not registered")?;
+        let report = (solution.run)("95-115,11-22", &Settings::default()).map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert_eq!(report.notes, vec!["11-22: 2 invalid (2 repeating 2x)", "95-115: 2 invalid (1 repeating 2x, 1 repeating 3x)"]);
+
+        let settings = Settings {
+            params: vec![("ranges".to_string(), "reject".to_string())],
+            ..Default::default()
+        };
+        assert!(matches!((solution.run)("11-22,20-30", &settings), Err(Failure::Running(_))));
+
+        let solution = find(2025, 1, 1).context("day 1 part 1 not registered")?;
+        let report = (solution.run)(DAY01, &Settings::default()).map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok(assert!(report.notes.is_empty()))
+    }
+
+    #[test]
+    fn parses_with_params() -> Result<()> {
+        let settings = |radix: &str| Settings {
+            params: vec![("radix".to_string(), radix.to_string())],
+            ..Settings::default()
+        };
+
+        // 0x11 to 0xff with two equal digits
+        let solution = find(2025, 2, 1).context("day 2 part 1 not registered")?;
+        let report = (solution.run)("10-ff", &settings("16")).map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert_eq!(report.answer, (17 * 120).to_string());
+        assert_eq!(report.notes, vec!["10-ff: 15 invalid (15 repeating 2x)"]);
+
+        assert!(matches!((solution.run)("10-ff", &settings("37")), Err(Failure::Running(_))));
+        assert!(matches!((solution.run)("10-ff", &settings("10")), Err(Failure::Generating(_))));
+        check_params(&[solution.takes], &settings("2"))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn reports_generator_failures() {
+        let solution = find(2025, 2, 1).unwrap();
+        let result = (solution.run)("11-x", &Settings::default());
+
+        assert!(matches!(result, Err(Failure::Generating(_))));
+    }
+
+    #[test]
+    fn runs_streams() -> Result<()> {
+        let stream = find_stream(2025, 1, 2).context("day 1 part 2 has no stream")?;
+        let report = (stream.run)(&mut DAY01.as_bytes(), &Settings::default())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert_eq!(report.answer, "6");
+        assert!(report.generator.is_none());
+
+        let stream = find_stream(2025, 8, 1).context("day 8 part 1 has no stream")?;
+        let settings = Settings {
+            params: vec![("connections".to_string(), "1".to_string())],
+            ..Settings::default()
+        };
+        let report = (stream.run)(&mut "0,0,0\n1,0,0\n5,5,5\n".as_bytes(), &settings)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert_eq!(report.answer, "2");
+        assert!(report.generator.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validates() -> Result<()> {
+        let validator = find_validator(2025, 9).context("day 9 has no validator")?;
+        assert!((validator.run)("7,1\n11,1\n11,7\n7,7")?.is_empty());
+        assert_eq!((validator.run)("7,1\n11,1\n11,7\n8,8")?.len(), 2);
+        assert!((validator.run)("7,x").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn renders() -> Result<()> {
+        let renderer = find_renderer(2025, 9).context("day 9 has no renderer")?;
+        assert_eq!((renderer.run)("7,1\n11,1\n11,7\n7,7")?.len(), 1);
+        assert!(find_renderer(2025, 1).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn solves_strings() -> Result<()> {
+        assert_eq!(solve_str(1, 2, DAY01)?, Answer("6".to_string()));
+        assert_eq!(solve_str(1, 3, DAY01)?, Answer("32".to_string()));
+        assert!(solve_str(1, 4, DAY01).is_err());
+        assert!(solve_str(2, 1, "11-x").is_err());
+
+        // nothing may panic on empty input
+        for solution in crate::y2025::SOLUTIONS {
+            let _ = solve_str(solution.day, solution.part, "");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn solves_concurrently() -> Result<()> {
+        let cases = [
+            (1, 1, DAY01.to_string()),
+            (1, 2, DAY01.to_string()),
+            (7, 2, "..S..\n.....\n..^..\n.....\n.^.^.\n.....".to_string()),
+            (7, 2, "..S..\n.....\n..^..\n.....\n.....\n.....".to_string()),
+            (11, 1, "you: a b\na: out\nb: out".to_string()),
+            (11, 2, "svr: fft dac\nfft: dac\ndac: out".to_string()),
+            (11, 2, "svr: a b\na: fft\nb: fft\nfft: dac\ndac: x y\nx: out\ny: out".to_string()),
+        ];
+        let expected = cases
+            .iter()
+            .map(|(day, part, input)| solve_str(*day, *part, input))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            expected.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+            vec!["3", "6", "4", "2", "2", "1", "4"]
+        );
+
+        std::thread::scope(|scope| {
+            let threads = (0..16)
+                .map(|t| {
+                    let (cases, expected) = (&cases, &expected);
+                    scope.spawn(move || -> Result<()> {
+                        for i in 0..cases.len() * 4 {
+                            let i = (i + t) % cases.len();
+                            let (day, part, input) = &cases[i];
+                            assert_eq!(solve_str(*day, *part, input)?, expected[i]);
+                        }
+                        Ok(())
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            threads.into_iter().try_for_each(|t| t.join().expect("thread panicked"))
+        })
+    }
+
+    #[test]
+    fn keys_solutions_by_year() {
+        assert!(find(2025, 12, 1).is_some());
+        assert!(find(2024, 1, 1).is_none());
+        assert!(season(2026).is_some());
+    }
+}
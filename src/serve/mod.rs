@@ -0,0 +1,419 @@
+use std::{
+    io::{self, ErrorKind, Read},
+    sync::{
+        Arc, mpsc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{
+    runner::{self, Failure, Report, Settings},
+    utils::Cancel,
+};
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Largest accepted puzzle input in bytes.
+    pub max_body: usize,
+    /// How long a request may take before it's answered with an error,
+    /// counted from when it got a worker, so reading the input counts too.
+    pub timeout: Duration,
+    /// Requests handled at once, requests beyond that are answered with 503
+    /// right away. A solver that doesn't check its [`Cancel`] keeps its
+    /// worker after timing out until it finishes, so slow inputs for such
+    /// days can take all of them for a while. The same goes for a client
+    /// that stops sending its input without closing the connection, as the
+    /// deadline is only checked between reads.
+    pub workers: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_body: 16 * 1024 * 1024,
+            timeout: Duration::from_secs(30),
+            workers: thread::available_parallelism().map_or(4, |n| n.get()),
+        }
+    }
+}
+
+/// Counts the request threads against [`Config::workers`].
+#[derive(Clone, Default)]
+struct Workers(Arc<AtomicUsize>);
+
+/// A claimed worker, given back when the thread holding it drops it.
+struct Worker(Arc<AtomicUsize>);
+
+impl Workers {
+    fn claim(&self, max: usize) -> Option<Worker> {
+        self.0
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |busy| (busy < max).then_some(busy + 1))
+            .ok()
+            .map(|_| Worker(self.0.clone()))
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Binds to localhost. Port 0 picks a free port, see [`Service::port`].
+pub struct Service {
+    server: Server,
+    config: Config,
+    workers: Workers,
+}
+
+impl Service {
+    pub fn bind(port: u16, config: Config) -> Result<Self> {
+        let server = Server::http(("127.0.0.1", port))
+            .map_err(|e| anyhow::anyhow!("{}", e))
+            .context("Could not bind server")?;
+
+        Ok(Self {
+            server,
+            config,
+            workers: Workers::default(),
+        })
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        self.server.server_addr().to_ip().map(|a| a.port())
+    }
+
+    /// Handles requests until the process ends, each on its own thread that
+    /// answers within [`Config::timeout`]. The worker is claimed before that
+    /// thread is spawned, so there are never more threads than workers.
+    pub fn run(self) {
+        for request in self.server.incoming_requests() {
+            let Some(worker) = self.workers.claim(self.config.workers) else {
+                respond(request, error(503, "All workers are busy, try again later"));
+                continue;
+            };
+            let config = self.config.clone();
+            thread::spawn(move || handle(request, &config, worker));
+        }
+    }
+}
+
+/// Answer of a request as a status code and a JSON body.
+type Reply = (u16, Value);
+
+fn error(status: u16, message: impl ToString) -> Reply {
+    (status, json!({ "error": message.to_string() }))
+}
+
+fn handle(mut request: Request, config: &Config, worker: Worker) {
+    let reply = reply(&mut request, config, worker).unwrap_or_else(|e| e);
+    respond(request, reply);
+}
+
+fn respond(request: Request, (status, body): Reply) {
+    let header = Header::from_bytes("Content-Type", "application/json").expect("valid header");
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    let _ = request.respond(response);
+}
+
+/// Parses `/solve/{day}/{part}` with an optional `?year=` query.
+fn route(url: &str) -> Result<(u32, u32, u32), Reply> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments = path.split('/').filter(|s| !s.is_empty()).collect::<Vec<_>>();
+
+    let (day, part) = match segments.as_slice() {
+        ["solve", day, part] => (
+            day.parse::<u32>().map_err(|_| error(400, "Invalid day"))?,
+            part.parse::<u32>().map_err(|_| error(400, "Invalid part"))?,
+        ),
+        _ => return Err(error(404, "Not found")),
+    };
+
+    let mut year = runner::DEFAULT_YEAR;
+    for (key, value) in query.split('&').filter_map(|q| q.split_once('=')) {
+        if key == "year" {
+            year = value.parse().map_err(|_| error(400, "Invalid year"))?;
+        }
+    }
+
+    Ok((year, day, part))
+}
+
+/// Fails every read once the deadline has passed, so a client sending its
+/// input slowly can't keep a worker past it.
+struct Deadline<'a, R> {
+    inner: R,
+    cancel: &'a Cancel,
+}
+
+impl<R: Read> Read for Deadline<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.cancel.is_cancelled() {
+            return Err(io::Error::new(ErrorKind::TimedOut, "Deadline passed"));
+        }
+        self.inner.read(buf)
+    }
+}
+
+fn read_body(request: &mut Request, max_body: usize, cancel: &Cancel, timeout: Duration) -> Result<String, Reply> {
+    if request.body_length().is_some_and(|l| l > max_body) {
+        return Err(error(413, format!("Input exceeds {} bytes", max_body)));
+    }
+
+    let mut body = vec![];
+    let reader = Deadline {
+        inner: request.as_reader(),
+        cancel,
+    };
+    reader
+        .take(max_body as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| match e.kind() {
+            ErrorKind::TimedOut => error(408, format!("Timed out reading the input after {:?}", timeout)),
+            _ => error(400, e),
+        })?;
+
+    if body.len() > max_body {
+        return Err(error(413, format!("Input exceeds {} bytes", max_body)));
+    }
+
+    let body = String::from_utf8(body).map_err(|_| error(400, "Input is not UTF-8"))?;
+    Ok(body.trim_end_matches('\n').to_string())
+}
+
+fn reply(request: &mut Request, config: &Config, worker: Worker) -> Result<Reply, Reply> {
+    // the deadline covers reading the input as well as solving it
+    let deadline = Instant::now() + config.timeout;
+    let cancel = Cancel::after(config.timeout);
+    let (year, day, part) = route(request.url())?;
+
+    if *request.method() != Method::Post {
+        return Err(error(405, "Use POST"));
+    }
+
+    let solution = runner::find(year, day, part)
+        .ok_or_else(|| error(404, format!("No solution for {} day {} part {}", year, day, part)))?;
+    let input = read_body(request, config.max_body, &cancel, config.timeout)?;
+
+    // Long-running solvers stop at the deadline by themselves, the others are
+    // cancelled as soon as possible and left to finish on their own thread,
+    // holding on to their worker until then.
+    let settings = Settings {
+        cancel: cancel.clone(),
+        ..Settings::default()
+    };
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = (solution.run)(&input, &settings);
+        // free before answering, so the client's next request finds it free
+        drop(worker);
+        let _ = tx.send(result);
+    });
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    let result = rx.recv_timeout(remaining).map_err(|_| {
+        cancel.cancel();
+        error(504, format!("Timed out after {:?}", config.timeout))
+    })?;
+    if result.as_ref().is_err_and(Failure::is_cancelled) {
+        return Err(error(504, format!("Timed out after {:?}", config.timeout)));
+    }
+
+    let meta = json!({ "year": year, "day": day, "part": part });
+    Ok(to_reply(meta, result))
+}
+
+fn to_reply(mut meta: Value, result: Result<Report, Failure>) -> Reply {
+    match result {
+        Ok(report) => {
+            meta["answer"] = json!(report.answer);
+            if !report.notes.is_empty() {
+                meta["notes"] = json!(report.notes);
+            }
+            meta["timings"] = json!({
+                "generator": report.generator.map(|g| g.time.as_secs_f64()),
+                "runner": report.runner.time.as_secs_f64(),
+            });
+            (200, meta)
+        }
+        Err(failure) => {
+            let (phase, e) = match failure {
+                Failure::Generating(e) => ("generating", e),
+                Failure::Running(e) => ("running", e),
+            };
+            meta["error"] = json!(format!("{:#}", e));
+            meta["phase"] = json!(phase);
+            (422, meta)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::Write,
+        net::TcpStream,
+    };
+
+    use super::*;
+
+    fn start(config: Config) -> Result<u16> {
+        let service = Service::bind(0, config)?;
+        let port = service.port().context("No port")?;
+        thread::spawn(move || service.run());
+        Ok(port)
+    }
+
+    fn post(port: u16, path: &str, body: &str) -> Result<(u16, Value)> {
+        let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            path,
+            body.len(),
+            body
+        )?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+
+        let status = response
+            .split_whitespace()
+            .nth(1)
+            .context("No status")?
+            .parse::<u16>()?;
+        let (_, body) = response.split_once("\r\n\r\n").context("No body")?;
+
+        Ok((status, serde_json::from_str(body)?))
+    }
+
+    #[test]
+    fn solves() -> Result<()> {
+        let port = start(Config::default())?;
+        let (status, body) = post(port, "/solve/1/2", "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n")?;
+
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], "6");
+        assert_eq!(body["year"], 2025);
+        assert!(body["timings"]["runner"].is_number());
+
+        Ok(())
+    }
+
+    #[test]
+    fn reports_errors() -> Result<()> {
+        let port = start(Config::default())?;
+
+        let (status, body) = post(port, "/solve/2/1", "11-x")?;
+        assert_eq!(status, 422);
+        assert_eq!(body["phase"], "generating");
+
+        let (status, _) = post(port, "/solve/13/1", "")?;
+        assert_eq!(status, 404);
+
+        let (status, _) = post(port, "/solve/1/1?year=1999", "")?;
+        assert_eq!(status, 404);
+
+        Ok(())
+    }
+
+    #[test]
+    fn limits_input_size() -> Result<()> {
+        let port = start(Config {
+            max_body: 8,
+            ..Config::default()
+        })?;
+
+        let (status, _) = post(port, "/solve/1/1", "L1\nL2\nL3\nL4\n")?;
+        Ok(assert_eq!(status, 413))
+    }
+
+    #[test]
+    fn times_out() -> Result<()> {
+        let port = start(Config {
+            timeout: Duration::from_millis(50),
+            ..Config::default()
+        })?;
+
+        // light 0 can't be switched on, so every combination of buttons is tried
+        let input = format!("[#.] {} {{1,1}}", "(1) ".repeat(24));
+        let (status, body) = post(port, "/solve/10/1", &input)?;
+        assert_eq!(status, 504);
+        assert!(body["error"].as_str().unwrap_or_default().contains("Timed out"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn times_out_reading() -> Result<()> {
+        let port = start(Config {
+            timeout: Duration::from_millis(50),
+            ..Config::default()
+        })?;
+
+        // tiny_http reads bodies of up to 1024 bytes before handing out the request
+        let body = "L1\n".repeat(1000);
+        let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+        write!(
+            stream,
+            "POST /solve/1/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        )?;
+        // the server may stop reading once the deadline has passed
+        for chunk in body.as_bytes().chunks(100) {
+            thread::sleep(Duration::from_millis(10));
+            let _ = stream.write_all(chunk);
+        }
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        assert!(response.starts_with("HTTP/1.1 408"), "{}", response);
+        assert!(response.contains("Timed out reading"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn limits_workers() -> Result<()> {
+        let port = start(Config {
+            workers: 0,
+            ..Config::default()
+        })?;
+        let (status, _) = post(port, "/solve/1/1", "L1
+")?;
+        assert_eq!(status, 503);
+
+        // a finished solve gives its worker back
+        let port = start(Config {
+            workers: 1,
+            ..Config::default()
+        })?;
+        for _ in 0..3 {
+            let (status, _) = post(port, "/solve/1/1", "L1
+")?;
+            assert_eq!(status, 200);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn claims_workers() {
+        let workers = Workers::default();
+        let first = workers.claim(2);
+        let second = workers.claim(2);
+        assert!(first.is_some() && second.is_some());
+        assert!(workers.claim(2).is_none());
+
+        drop(first);
+        assert!(workers.claim(2).is_some());
+    }
+}
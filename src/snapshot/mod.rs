@@ -0,0 +1,104 @@
+//! Snapshot tests compare text against `tests/snapshots/<name>.txt`. Run the
+//! tests with `UPDATE_SNAPSHOTS=1` to write new or changed snapshots instead,
+//! and review the result with `git diff tests/snapshots`.
+
+use std::{env, fs, path::PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+/// Lines of context shown around each difference.
+const CONTEXT: usize = 2;
+
+fn path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.txt", name))
+}
+
+fn updating() -> bool {
+    env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Fails with a diff if `actual` doesn't match the snapshot `name`.
+pub fn compare_snapshot(name: &str, actual: &str) -> Result<()> {
+    let path = path(name);
+
+    if updating() {
+        fs::create_dir_all(path.parent().context("Snapshot has no directory")?)?;
+        return fs::write(&path, actual).context(format!("Could not write {}", path.display()));
+    }
+
+    let expected = fs::read_to_string(&path).context(format!(
+        "Could not read {}, run with UPDATE_SNAPSHOTS=1 to create it",
+        path.display()
+    ))?;
+    if expected == actual {
+        return Ok(());
+    }
+
+    bail!(
+        "Snapshot {} differs, run with UPDATE_SNAPSHOTS=1 to accept the changes\n{}",
+        name,
+        diff(&expected, actual)
+    )
+}
+
+/// Line by line comparison with the differing lines marked `-` (expected) and
+/// `+` (actual). Good enough for renderings that keep their size.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let len = expected.len().max(actual.len());
+    let differs = |i: usize| expected.get(i) != actual.get(i);
+
+    let mut out = String::new();
+    let mut last = None;
+    for i in 0..len {
+        if !(i.saturating_sub(CONTEXT)..(i + CONTEXT + 1).min(len)).any(differs) {
+            continue;
+        }
+        if last.is_some_and(|l| l + 1 != i) || (last.is_none() && i > 0) {
+            out.push_str("...\n");
+        }
+        last = Some(i);
+
+        if !differs(i) {
+            out.push_str(&format!("  {:4} {}\n", i + 1, expected[i]));
+            continue;
+        }
+        if let Some(line) = expected.get(i) {
+            out.push_str(&format!("- {:4} {}\n", i + 1, line));
+        }
+        if let Some(line) = actual.get(i) {
+            out.push_str(&format!("+ {:4} {}\n", i + 1, line));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn diffs_lines() {
+        let expected = "a\nb\nc\nd\ne\nf\ng";
+        let actual = "a\nb\nc\nd\nE\nf\ng\nh";
+
+        assert_eq!(
+            diff(expected, actual),
+            "...\n     3 c\n     4 d\n-    5 e\n+    5 E\n     6 f\n     7 g\n+    8 h\n"
+        );
+    }
+
+    #[test]
+    fn reports_missing_snapshots() {
+        if updating() {
+            return;
+        }
+
+        let e = compare_snapshot("does-not-exist", "").unwrap_err();
+        assert!(format!("{:#}", e).contains("UPDATE_SNAPSHOTS=1"));
+    }
+}
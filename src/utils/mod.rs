@@ -1,8 +1,148 @@
+use std::{
+    io::BufRead,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
 use thiserror::Error;
 
+/// Arbitrary-precision unsigned integer for counts that outgrow `u128`.
+pub use num_bigint::BigUint;
+
 #[derive(Error, Debug)]
 pub enum AocError {
     #[error("Generic error")]
     GenericError,
+    #[error("Arithmetic overflow")]
+    Overflow,
+    #[error("Unknown parameter {0}")]
+    UnknownParameter(String),
+    #[error("Cancelled")]
+    Cancelled,
+}
+
+/// A semantic problem in an input that parsed fine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// What the problem refers to, e.g. `machine 3`.
+    pub location: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(location: impl ToString, message: impl ToString) -> Self {
+        Self {
+            location: location.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// Receives progress updates from long-running solvers. Solvers report
+/// every now and then, not on every item, so implementations may do some work.
+pub trait Progress: Send + Sync {
+    /// `done` items processed out of an estimated `total`.
+    fn report(&self, done: u64, total: u64);
+}
+
+/// Ignores all progress.
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn report(&self, _done: u64, _total: u64) {}
+}
+
+/// Extra lines a solver reports next to its answer, like statistics of the
+/// input. Solvers that take `notes` get a fresh collection on every run.
+#[derive(Debug, Default)]
+pub struct Notes(Mutex<Vec<String>>);
+
+impl Notes {
+    pub fn add(&self, note: impl ToString) {
+        if let Ok(mut notes) = self.0.lock() {
+            notes.push(note.to_string());
+        }
+    }
+
+    pub fn into_lines(self) -> Vec<String> {
+        self.0.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Cooperative cancellation for long-running loops. Clones share the same
+/// flag, so cancelling one cancels all of them.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel {
+    flag: Arc<AtomicBool>,
+    deadline: Option<Instant>,
 }
 
+impl Cancel {
+    /// A token that is cancelled automatically once `timeout` has passed.
+    pub fn after(timeout: Duration) -> Self {
+        Self {
+            flag: Arc::default(),
+            deadline: Instant::now().checked_add(timeout),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed) || self.deadline.is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// Fails with [`AocError::Cancelled`] once cancelled.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(AocError::Cancelled)?
+        }
+        Ok(())
+    }
+}
+
+/// Puzzle parameters of a day. `Default` holds the values of the actual
+/// puzzle, individual values can be overridden with `key=value` pairs.
+pub trait Params: Default {
+    fn set(&mut self, key: &str, value: &str) -> Result<()>;
+
+    fn with_overrides(overrides: &[(String, String)]) -> Result<Self> {
+        let mut params = Self::default();
+        for (key, value) in overrides {
+            params
+                .set(key, value)
+                .context(format!("Could not set {} to {}", key, value))?;
+        }
+        Ok(params)
+    }
+}
+
+/// Splits a `key=value` override.
+pub fn parse_override(s: &str) -> Result<(String, String)> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or(AocError::GenericError)
+        .context("Expected key=value")?;
+    Ok((key.trim().to_string(), value.trim().to_string()))
+}
+
+/// Non-empty lines of `reader`, read one at a time so memory stays bounded
+/// by the longest line.
+pub fn stream_lines(reader: impl BufRead) -> impl Iterator<Item = Result<String>> {
+    reader
+        .lines()
+        .map(|l| Ok(l?))
+        .filter(|l| !matches!(l, Ok(l) if l.is_empty()))
+}
@@ -0,0 +1,330 @@
+use std::{fmt::Write, str::FromStr};
+
+use anyhow::{Context, Error, Result};
+
+use crate::utils::AocError;
+
+/// Colours are picked by meaning, each backend maps them to its own palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// Things that are out of play, like removed rolls.
+    Dim,
+    /// The puzzle input itself.
+    Primary,
+    /// What changed in this frame or what the answer is made of.
+    Highlight,
+    /// One of several categories, e.g. different presents.
+    Accent(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    /// One grid tile, `x` being the column and `y` the row.
+    Cell { x: i64, y: i64, glyph: char, color: Color },
+    /// Outline of an axis aligned rectangle covering the tiles between both corners.
+    Rect { from: (i64, i64), to: (i64, i64), color: Color },
+    /// Closed outline through the centers of the given tiles.
+    Polygon { points: Vec<(i64, i64)>, color: Color },
+}
+
+/// A snapshot of a day's state on a `width` by `height` tile grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub title: String,
+    pub width: i64,
+    pub height: i64,
+    pub items: Vec<Item>,
+}
+
+impl Frame {
+    pub fn new(title: impl ToString, width: i64, height: i64) -> Self {
+        Self {
+            title: title.to_string(),
+            width,
+            height,
+            items: vec![],
+        }
+    }
+
+    pub fn cell(&mut self, x: i64, y: i64, glyph: char, color: Color) {
+        self.items.push(Item::Cell { x, y, glyph, color });
+    }
+
+    pub fn rect(&mut self, from: (i64, i64), to: (i64, i64), color: Color) {
+        self.items.push(Item::Rect { from, to, color });
+    }
+
+    pub fn polygon(&mut self, points: Vec<(i64, i64)>, color: Color) {
+        self.items.push(Item::Polygon { points, color });
+    }
+}
+
+/// Renders the frames of a day, see [`crate::runner::Renderer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Coloured text for the terminal, large frames are scaled down to fit.
+    Ansi,
+    /// Like [`Backend::Ansi`] without colours, for files and snapshots.
+    Text,
+    /// A single SVG document with the frames stacked top to bottom.
+    Svg,
+}
+
+impl FromStr for Backend {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ansi" => Ok(Backend::Ansi),
+            "text" => Ok(Backend::Text),
+            "svg" => Ok(Backend::Svg),
+            _ => Err(AocError::GenericError).context(format!("Unknown backend {}, use ansi, text or svg", s)),
+        }
+    }
+}
+
+impl Backend {
+    pub fn render(&self, frames: &[Frame]) -> String {
+        match self {
+            Backend::Ansi => frames.iter().map(|f| text(f, true)).collect::<Vec<_>>().join("\n"),
+            Backend::Text => frames.iter().map(|f| text(f, false)).collect::<Vec<_>>().join("\n"),
+            Backend::Svg => svg(frames),
+        }
+    }
+}
+
+/// Largest terminal area a frame is drawn on.
+const ANSI_COLUMNS: i64 = 160;
+const ANSI_ROWS: i64 = 80;
+
+fn ansi_code(color: Color) -> u8 {
+    match color {
+        Color::Dim => 90,
+        Color::Primary => 97,
+        Color::Highlight => 91,
+        Color::Accent(i) => 32 + (i % 5) as u8,
+    }
+}
+
+/// Visits every tile on the straight line between `a` and `b`.
+fn line(a: (i64, i64), b: (i64, i64), mut visit: impl FnMut(i64, i64)) {
+    let steps = (b.0 - a.0).abs().max((b.1 - a.1).abs());
+    if steps == 0 {
+        return visit(a.0, a.1);
+    }
+
+    for s in 0..=steps {
+        let x = a.0 + ((b.0 - a.0) * s * 2 + steps).div_euclid(2 * steps);
+        let y = a.1 + ((b.1 - a.1) * s * 2 + steps).div_euclid(2 * steps);
+        visit(x, y);
+    }
+}
+
+fn outline(item: &Item) -> Vec<((i64, i64), (i64, i64))> {
+    match item {
+        Item::Cell { .. } => vec![],
+        Item::Rect { from, to, .. } => {
+            let corners = [*from, (to.0, from.1), *to, (from.0, to.1)];
+            (0..4).map(|i| (corners[i], corners[(i + 1) % 4])).collect()
+        }
+        Item::Polygon { points, .. } => (0..points.len())
+            .map(|i| (points[i], points[(i + 1) % points.len()]))
+            .collect(),
+    }
+}
+
+fn text(frame: &Frame, colors: bool) -> String {
+    let scale = 1
+        .max((frame.width + ANSI_COLUMNS - 1) / ANSI_COLUMNS)
+        .max((frame.height + ANSI_ROWS - 1) / ANSI_ROWS);
+    let (columns, rows) = ((frame.width + scale - 1) / scale, (frame.height + scale - 1) / scale);
+    let mut screen = vec![vec![(' ', Color::Primary); columns.max(0) as usize]; rows.max(0) as usize];
+
+    let shrink = |(x, y): (i64, i64)| (x.div_euclid(scale), y.div_euclid(scale));
+    let mut plot = |x: i64, y: i64, glyph: char, color: Color| {
+        if (0..columns).contains(&x) && (0..rows).contains(&y) {
+            screen[y as usize][x as usize] = (glyph, color);
+        }
+    };
+
+    for item in &frame.items {
+        match item {
+            Item::Cell { x, y, glyph, color } => {
+                let (x, y) = shrink((*x, *y));
+                plot(x, y, *glyph, *color)
+            }
+            Item::Rect { color, .. } | Item::Polygon { color, .. } => {
+                for (a, b) in outline(item) {
+                    line(shrink(a), shrink(b), |x, y| plot(x, y, '#', *color));
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    if colors {
+        let _ = write!(out, "\x1b[1m{}\x1b[0m", frame.title);
+    } else {
+        out.push_str(&frame.title);
+    }
+    if scale > 1 {
+        let _ = write!(out, " (1:{})", scale);
+    }
+    out.push('\n');
+
+    for row in screen {
+        let mut current = None;
+        for (glyph, color) in row {
+            if colors && glyph != ' ' && current != Some(color) {
+                let _ = write!(out, "\x1b[{}m", ansi_code(color));
+                current = Some(color);
+            }
+            out.push(glyph);
+        }
+        if colors {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Size of a tile in SVG user units.
+const SVG_TILE: i64 = 10;
+const SVG_TITLE: i64 = 20;
+
+fn svg_color(color: Color) -> &'static str {
+    const ACCENTS: [&str; 5] = ["#2a9d8f", "#e9c46a", "#f4a261", "#457b9d", "#8d5fd3"];
+
+    match color {
+        Color::Dim => "#c8c8c8",
+        Color::Primary => "#264653",
+        Color::Highlight => "#e63946",
+        Color::Accent(i) => ACCENTS[i % ACCENTS.len()],
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn svg(frames: &[Frame]) -> String {
+    // huge frames like the day 9 polygon are scaled to about a thousand units
+    let scale = |frame: &Frame| 1.0f64.min(1000.0 / (frame.width.max(frame.height).max(1) * SVG_TILE) as f64);
+    let size = |frame: &Frame| {
+        let s = scale(frame) * SVG_TILE as f64;
+        (frame.width as f64 * s, frame.height as f64 * s)
+    };
+
+    let width = frames.iter().map(|f| size(f).0).fold(0.0, f64::max);
+    let height = frames.iter().map(|f| size(f).1 + SVG_TITLE as f64).sum::<f64>();
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="monospace">"#,
+        w = width,
+        h = height
+    );
+
+    let mut top = 0.0;
+    for frame in frames {
+        let s = scale(frame) * SVG_TILE as f64;
+        let center = |(x, y): (i64, i64)| format!("{},{}", (x as f64 + 0.5) * s, (y as f64 + 0.5) * s);
+
+        let _ = writeln!(out, r#"<text x="0" y="{}" font-size="14">{}</text>"#, top + 15.0, escape(&frame.title));
+        let _ = writeln!(out, r#"<g transform="translate(0 {})">"#, top + SVG_TITLE as f64);
+
+        for item in &frame.items {
+            let _ = match item {
+                Item::Cell { x, y, color, .. } => writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                    *x as f64 * s,
+                    *y as f64 * s,
+                    svg_color(*color),
+                ),
+                Item::Rect { from, to, color } => writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}"/>"#,
+                    from.0.min(to.0) as f64 * s,
+                    from.1.min(to.1) as f64 * s,
+                    ((to.0 - from.0).abs() + 1) as f64 * s,
+                    ((to.1 - from.1).abs() + 1) as f64 * s,
+                    svg_color(*color),
+                ),
+                Item::Polygon { points, color } => writeln!(
+                    out,
+                    r#"<polygon points="{}" fill="none" stroke="{}"/>"#,
+                    points.iter().map(|p| center(*p)).collect::<Vec<_>>().join(" "),
+                    svg_color(*color),
+                ),
+            };
+        }
+
+        out.push_str("</g>\n");
+        top += size(frame).1 + SVG_TITLE as f64;
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frame() -> Frame {
+        let mut frame = Frame::new("test", 4, 3);
+        frame.cell(0, 0, '@', Color::Primary);
+        frame.cell(3, 2, 'x', Color::Highlight);
+        frame.rect((1, 0), (2, 1), Color::Dim);
+        frame
+    }
+
+    fn strip(s: &str) -> String {
+        let mut out = String::new();
+        let mut escape = false;
+        for c in s.chars() {
+            match c {
+                '\x1b' => escape = true,
+                'm' if escape => escape = false,
+                _ if !escape => out.push(c),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn text() -> Result<()> {
+        let out = Backend::from_str("text")?.render(&[frame()]);
+        assert_eq!(out, "test\n@## \n ## \n   x\n");
+        assert_eq!(strip(&Backend::from_str("ansi")?.render(&[frame()])), out);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scales_large_frames() {
+        let mut frame = Frame::new("large", 1600, 10);
+        frame.polygon(vec![(0, 0), (1599, 0), (1599, 9), (0, 9)], Color::Primary);
+
+        let out = Backend::Text.render(&[frame]);
+        assert_eq!(out, format!("large (1:10)\n{}\n", "#".repeat(160)));
+    }
+
+    #[test]
+    fn svg() -> Result<()> {
+        let out = Backend::from_str("svg")?.render(&[frame(), frame()]);
+
+        assert!(out.starts_with("<svg"));
+        assert_eq!(out.matches("<g ").count(), 2);
+        assert_eq!(out.matches("<rect").count(), 6);
+        assert!(out.contains(r##"<rect x="10" y="0" width="20" height="20" fill="none" stroke="#c8c8c8"/>"##));
+        assert!(Backend::from_str("png").is_err());
+
+        Ok(())
+    }
+}
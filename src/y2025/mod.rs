@@ -0,0 +1,61 @@
+//! The 2025 days live at the crate root, where `aoc_lib!` and cargo-aoc expect them.
+//! This module only lists them for the runner.
+
+use crate::runner::{Renderer, Solution, Stream, Validator, renderer, solution, stream, validator};
+use crate::day01::lock;
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12};
+
+pub static SOLUTIONS: &[Solution] = &[
+    solution!(1, 1, day01::solve_part1_with(params)),
+    solution!(1, 2, day01::solve_part2_with(params)),
+    // the multi-dial lock, see day01::lock
+    solution!(1, 3, lock::solve_with(params, notes)),
+    solution!(2, 1, day02::input_generator_with(params) => day02::solve_part1_with(params, cancel, progress, notes)),
+    solution!(2, 2, day02::input_generator_with(params) => day02::solve_part2_with(params, cancel, progress, notes)),
+    solution!(3, 1, day03::solve_part1),
+    solution!(3, 2, day03::solve_part2_with(params)),
+    solution!(4, 1, day04::solve_part1),
+    solution!(4, 2, day04::solve_part2),
+    solution!(5, 1, day05::solve_part1),
+    solution!(5, 2, day05::solve_part2),
+    solution!(6, 1, day06::solve_part1),
+    solution!(6, 2, day06::solve_part2),
+    solution!(7, 1, day07::solve_part1),
+    solution!(7, 2, day07::solve_part2),
+    solution!(8, 1, day08::solve_part1_with(params)),
+    solution!(8, 2, day08::solve_part2_with(progress)),
+    solution!(9, 1, day09::solve_part1),
+    solution!(9, 2, day09::solve_part2),
+    solution!(10, 1, day10::solve_part1_with(cancel)),
+    solution!(10, 2, day10::solve_part2),
+    solution!(11, 1, day11::solve_part1_with(params, cancel)),
+    solution!(11, 2, day11::solve_part2_with(params)),
+    solution!(12, 1, day12::solve_part1),
+    solution!(12, 2, day12::solve_part2),
+];
+
+pub static STREAMS: &[Stream] = &[
+    stream!(1, 1, day01::solve_part1_stream(params)),
+    stream!(1, 2, day01::solve_part2_stream(params)),
+    stream!(3, 1, day03::solve_part1_stream),
+    stream!(3, 2, day03::solve_part2_stream(params)),
+    stream!(5, 1, day05::solve_part1_stream),
+    stream!(8, 1, day08::input_generator_stream => day08::solve_part1_with(params)),
+    stream!(8, 2, day08::input_generator_stream => day08::solve_part2_with(progress)),
+    stream!(10, 1, day10::solve_part1_stream(cancel)),
+    stream!(10, 2, day10::solve_part2_stream),
+];
+
+pub static VALIDATORS: &[Validator] = &[
+    validator!(6, day06),
+    validator!(9, day09),
+    validator!(10, day10),
+    validator!(12, day12),
+];
+
+pub static RENDERERS: &[Renderer] = &[
+    renderer!(4, day04),
+    renderer!(7, day07),
+    renderer!(9, day09),
+    renderer!(12, day12),
+];
@@ -0,0 +1,16 @@
+//! Days of the 2026 event.
+//!
+//! `aoc_lib!` only knows a single year, so days in here don't use the
+//! `#[aoc]`/`#[aoc_generator]` attributes. Add them as `y2026::dayNN` modules
+//! with an `input_generator` and `solve_partN` functions and register them
+//! below with `solution!(day, part, dayNN::solve_partN)`.
+
+use crate::runner::{Renderer, Solution, Stream, Validator};
+
+pub static SOLUTIONS: &[Solution] = &[];
+
+pub static STREAMS: &[Stream] = &[];
+
+pub static VALIDATORS: &[Validator] = &[];
+
+pub static RENDERERS: &[Renderer] = &[];